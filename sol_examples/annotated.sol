@@ -1,20 +1,61 @@
 ﻿[global]
 name = Klondike (hard)
-# Number of decks: 1 or 2
+# Optional: chance of winning 1 of N. Run `solkit --estimate-chance FILE --save-chance` to fill it
+# chance = 4
+# Number of decks: all decks together may have at most 16 suits (e.g., 4 full decks
+# or 8 single-suit ones)
 decks = 1
+# Optional: ranks of a single deck:
+#   'full' - all 13 ranks (default)
+#   'piquet' or '32' - A, 7, 8, 9, 10, J, Q, K
+#   '36' - A, 6, 7, 8, 9, 10, J, Q, K
+#   or a list of faces, e.g. 'a 8 9 10 j q k'
+# Missing ranks are skipped when building a sequence: in a Piquet deck 7 goes on A
+# ranks = full
+# Optional: suits of a single deck: 'all' (default) or a list, e.g. 'spades hearts'
+# suits = all
+# Optional: whether K and A are neighbors in sequences (i.e., A can go on K and K can go on A):
+#   'yes' (default) or 'no'
+# wrap = yes
+# Optional: 'low' - A goes before 2 (default), 'high' - A goes after K
+# ace = low
+# Optional: the number of wild cards in a single deck: 0 (default), 1, or 2.
+# Not allowed in pairing solitaires and when foundations get only full runs
+# jokers = 0
+# Optional: what cards must be moved to foundations to win:
+#   'all' - all cards (default)
+#   'tableau' - all cards except the ones left in deck and waste (as in Pyramid and Golf)
+# goal = all
 
 # Describes a pile of extra face-down cards as in left-top corner in Klondike.
 # Some solitaries do noty have it.
 [deck]
-# 'unlimited' or number of redeals. Used only if 'deal_to' is 'waste'
+# 'unlimited' or number of redeals. Used only if 'deal_to' is 'waste' or 'redeal_mode' is 'shuffle'
 redeals = unlimited
 # Number of cards moved to waste per click.
 # If deal_to is 'play area', the number always equals the number of columns in play area.
 deal_by = 3
+# Optional: number of cards moved to waste per click after the first redeal (0 - the same as deal_by, default)
+# redeal_by = 0
+# Optional: how the deck is refilled on redeal:
+#   'turn' - the waste is turned over, cards come out in the same order (default)
+#   'keep' - the waste goes to the deck in reverse order: the last dealt card comes out first
+#   'shuffle' - cards of all columns and the waste are shuffled and dealt to columns again as at
+#     the game start. It works with 'deal_to = columns' as well
+# redeal_mode = turn
 # Where to move cards from the deck: to waste or to play area columns.
 # 'waste', 'deck', and 'side' = to waste
 # 'columns' = to play area columns
+# 'foundation' = to the first foundation (as in Golf)
 deal_to = waste
+# Optional: what columns get cards if deal_to is 'columns':
+#   'all' - one card to every column (default)
+#   'empty' - one card to every empty column
+#   list of column numbers starting from 1, e.g. '1, 2, 3' - one card to every listed column
+#   'single N' - 'deal_by' cards to the column N
+# deal_cols = all
+# Optional: dealing to columns is allowed only if no column is empty ('yes' or 'no', default is 'no')
+# require_filled = no
 
 # Must be at least one slot (and no more than 8 slots)
 # From leftmost to rightmost column.
@@ -41,11 +82,42 @@ column = A, any, ascending, same suit
 column = A, any, ascending, same suit
 column = A, any, ascending, same suit
 column = A, any, ascending, same suit
+# Optional: how cards get to foundations:
+#   'card' - a player moves cards one by one (default)
+#   'sequence' - a full run of the same suit from K to A is moved from a column automatically
+#   'sum N' - two cards which values sum to N are removed together, a card of value N is removed
+#             alone (A is 1, J is 11, Q is 12, K is 13). E.g, Pyramid uses 'sum 13'
+#   'pairs' - two cards of the same face are removed together (as in Monte Carlo)
+# collect = card
+# Optional: 'yes' - the top card of a foundation can be moved back to a column or a free cell.
+# Works only with 'collect = card'. Default: 'no'
+# fnd_playable = no
+# Optional: points lost for every card taken back from a foundation. Default: 0
+# fnd_penalty = 0
 
 # Free cells configuration: most of solitaries do not have it.
 #[temp]
 # the number of free cells: no more than 4 slots
 # slots = 0
+# Optional: maximum number of cards in a slot, a number or 'unlimited' (default is 1)
+# limit = 1
+# Optional: order of cards put to a slot, the same values as 'order' in [column] section.
+# Required if a slot that accepts cards can hold more than one card
+# order = desc, alternate
+# Optional: the number of face-up cards dealt to every slot at start (default is 0)
+# deal = 0
+# Optional: which slots accept cards: 'all', 'none', or the number of the only slot that
+# accepts cards. By default, it is 'all' if no cards are dealt to slots, and 'none' otherwise
+# refillable = all
+
+# Reserve configuration: a pile where only the top card is playable. Most of solitaries do not have it.
+#[reserve]
+# the number of cards dealt to the reserve
+# cards = 13
+# Optional: the number of face-up cards (default is 1)
+# up = 1
+# Optional: move the top card to a column automatically when the column gets empty (default is 'no')
+# refill_columns = yes
 
 # Column area configuration
 [column]
@@ -53,6 +125,8 @@ column = A, any, ascending, same suit
 #   'top' - only the top card of a pile
 #   'ordered' - a few top cards of a pile can be moved only of they follow colum sort orders
 #   'any' - any pile of face-up cards can be moved to another pile
+#   'suited' - the same as 'ordered' but all cards must be of the same suit as well
+#   'supermove' - the same as 'ordered' but a run is limited to (free cells + 1) * 2^(empty columns) cards
 playable_card = any
 # What card face can start the pile if it gets empty:
 #   'any' - any card can start the pile
@@ -71,12 +145,30 @@ refill = K
 #    'none' or 'disable' - no card can be put to this pile from another pile. Useful to
 #           create "take-only" prefilled columns (like the first one in 'American toad')
 order = descending, alternate color
+# Optional: how columns are placed:
+#   'columns' - side by side (default)
+#   'pyramid' - every column holds a card of a pyramid, and the card is playable only when both
+#               cards of the next row covering it are removed. The number of columns must be
+#               1, 3, 6, 10, 15, 21, or 28
+#   'grid N' - columns make a grid with N cards in a row; only neighbors can be removed by pairs
+# layout = columns
+# Optional: free layout - the position of every column (one line per column, as in TriPeaks):
+# 1. Horizontal position in halves of a card width (0..20)
+# 2. Vertical position in rows (0..6): a card of the next row covers the lower part of the card
+# 3. Optional space-separated list of columns (starting from 1) that lie on top of this one. The
+#    column is unplayable until they are empty. Its face-down cards are turned when it is uncovered
+# place = 1, 0, 2 3
 # Intial column configuration. Contains two or three values:
 # 1. Total number of cards
 # 2. Number of face-up cards (must be at least 1 if a pile is not empty)
 # 3. Optional value with the single choice: 'take' or 'take only' - cards can be put from
 #    this pile anywhere, but putting cards to this pile is forbidden. Used in few solitaries,
 #    e.g, the first play area column in 'American toad'.
+# Then a few optional rules that override the common ones above for this column only:
+#   'order FACE-ORDER SUIT-ORDER', e.g. 'order asc same suit'
+#   'refill FACE', e.g. 'refill a'
+#   'playable PLAYABLE', e.g. 'playable top'
+# Example: column = 4, 4, take only, playable top
 column = 1, 1
 column = 2, 1
 column = 3, 1
@@ -56,11 +56,7 @@ impl ChooseStg {
 fn shift_in(s: &str, w: u16) -> u16 {
     let md = s.len() as u16 / 2;
     let wmd = w / 2;
-    if wmd < md {
-        0
-    } else {
-        wmd - md
-    }
+    wmd.saturating_sub(md)
 }
 
 impl Strategy for ChooseStg {
@@ -73,12 +69,10 @@ impl Strategy for ChooseStg {
         match event {
             Event::Key(ev) => match ev.code {
                 KeyCode::Esc => return Ok(Transition::Exit),
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if self.selected != 0 {
-                        self.selected -= 1;
-                        if self.selected < self.top {
-                            self.top = self.selected;
-                        }
+                KeyCode::Up | KeyCode::Char('k') if self.selected != 0 => {
+                    self.selected -= 1;
+                    if self.selected < self.top {
+                        self.top = self.selected;
                     }
                 }
                 KeyCode::Home | KeyCode::Char('K') => {
@@ -105,24 +99,25 @@ impl Strategy for ChooseStg {
                 }
                 KeyCode::PageDown | KeyCode::Char('d') => {
                     if self.selected + list_h >= l {
-                        self.top = if l > list_h { l - list_h } else { 0 };
+                        self.top = l.saturating_sub(list_h);
                         self.selected = l - 1;
                     } else {
                         self.top += list_h;
                         self.selected += list_h;
                     }
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if self.selected != l - 1 {
-                        if self.selected - self.top >= list_h {
-                            self.top += 1;
-                        }
-                        self.selected += 1;
+                KeyCode::Down | KeyCode::Char('j') if self.selected != l - 1 => {
+                    if self.selected - self.top >= list_h {
+                        self.top += 1;
                     }
+                    self.selected += 1;
                 }
 
                 KeyCode::Enter => {
                     ctx.name = self.sols[self.selected as usize].clone();
+                    if !ctx.custom && ctx.saves.has(&ctx.name) {
+                        return Ok(Transition::Push(TransitionStage::ResumeDialog));
+                    }
                     return Ok(Transition::Replace(TransitionStage::Play));
                 }
 
@@ -218,6 +213,6 @@ impl Strategy for ChooseStg {
         Ok(())
     }
 
-    fn on_activate(&self, _ctx: &mut Context) {}
+    fn on_activate(&mut self, _ctx: &mut Context) {}
     fn on_deactivate(&self, _ctx: &mut Context) {}
 }
@@ -4,6 +4,8 @@ use std::path::{Path, PathBuf};
 
 const CONF_FILE: &str = "config.toml";
 const STAT_FILE: &str = "stats.toml";
+const SAVE_FILE: &str = "saves.toml";
+const REPLAY_DIR: &str = "replays";
 const DEV_NAME: &str = "rionnag";
 const APP_NAME: &str = "solkit";
 
@@ -77,3 +79,18 @@ pub fn user_conf_path() -> PathBuf {
     p.push(CONF_FILE);
     p
 }
+
+// Returns path to the file with unfinished games
+pub fn saves_path() -> PathBuf {
+    let mut p = base_path();
+    p.push(SAVE_FILE);
+    p
+}
+
+// Returns path to the directory with saved replays
+pub fn replays_path() -> PathBuf {
+    let mut p = base_path();
+    p.push(REPLAY_DIR);
+    ensure_path_exists(&p);
+    p
+}
@@ -0,0 +1,154 @@
+use std::fs;
+use std::io::{stderr, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+
+use solkit::solver::{self, Limits, Outcome};
+
+use crate::opts::CliOpts;
+use crate::solve::find_conf;
+
+// default number of deals to solve
+const DEALS: u64 = 1000;
+// default limits per deal: estimation solves a lot of deals, so every deal gets less time
+const NODES: usize = 200_000;
+const TIME: u64 = 10;
+
+// set "chance" option in "[global]" section of the rules. The option is replaced if it exists,
+// otherwise it is added right after the section header
+fn set_chance(text: &str, chance: u16) -> String {
+    let eol = if text.contains("\r\n") { "\r\n" } else { "\n" };
+    let opt = format!("chance = {}", chance);
+    let mut lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+    let section = |line: &str| {
+        let low = line.trim_start_matches('\u{feff}').trim().to_lowercase();
+        if low.starts_with('[') {
+            Some(low.trim_matches(|c| c == '[' || c == ']' || c == ' ').to_string())
+        } else {
+            None
+        }
+    };
+
+    let mut header: Option<usize> = None;
+    let mut replaced = false;
+    for (idx, line) in lines.iter_mut().enumerate() {
+        if let Some(name) = section(line) {
+            if header.is_some() {
+                break;
+            }
+            if name == "global" {
+                header = Some(idx);
+            }
+            continue;
+        }
+        if header.is_none() {
+            continue;
+        }
+        let low = line.trim().to_lowercase();
+        if let Some(pos) = low.find('=') {
+            if low[..pos].trim() == "chance" {
+                *line = opt.clone();
+                replaced = true;
+                break;
+            }
+        }
+    }
+    if !replaced {
+        match header {
+            Some(idx) => lines.insert(idx + 1, opt),
+            None => {
+                // keep BOM at the very beginning of the file
+                let first = if lines.is_empty() { String::new() } else { lines.remove(0) };
+                let bom = if first.starts_with('\u{feff}') { "\u{feff}" } else { "" };
+                lines.insert(0, first.trim_start_matches('\u{feff}').to_string());
+                lines.insert(0, opt);
+                lines.insert(0, format!("{}[global]", bom));
+            }
+        }
+    }
+    let mut res = lines.join(eol);
+    res.push_str(eol);
+    res
+}
+
+fn write_chance(filename: &str, chance: u16) -> Result<()> {
+    let text = fs::read_to_string(filename)?;
+    fs::write(filename, set_chance(&text, chance))?;
+    Ok(())
+}
+
+// solve a lot of deals and print the chance of winning the solitaire
+pub(crate) fn run(cli: &CliOpts, target: &str) -> Result<()> {
+    let is_file = Path::new(target).is_file();
+    if cli.save_chance && !is_file {
+        return Err(anyhow!("The chance can be saved only to a rules file"));
+    }
+    let conf = find_conf(target)?;
+    let deals = cli.deals.unwrap_or(DEALS);
+    let limits = Limits {
+        nodes: cli.nodes.unwrap_or(NODES),
+        time: match cli.time.unwrap_or(TIME) {
+            0 => None,
+            tm => Some(Duration::from_secs(tm)),
+        },
+    };
+
+    println!("Solitaire: {}", conf.name);
+    let mut err = stderr();
+    let est = solver::estimate(&conf, 1..deals + 1, &limits, |seed, solution| {
+        let res = match solution.outcome {
+            Outcome::Solved(_) => "solved",
+            Outcome::Unsolvable => "lost",
+            Outcome::GaveUp => "gave up",
+        };
+        let _ = write!(err, "\rDeal {} of {}: {}      ", seed, deals, res);
+        let _ = err.flush();
+    })?;
+    eprintln!();
+
+    let (low, high) = est.interval();
+    println!("Deals: {}", est.deals);
+    println!("Won: {}, lost: {}, gave up: {}", est.solved, est.unsolvable, est.gave_up);
+    println!(
+        "Win rate: {:.1}% (95% confidence interval: {:.1}% - {:.1}%)",
+        est.rate() * 100.0,
+        low * 100.0,
+        high * 100.0
+    );
+    if est.gave_up != 0 {
+        println!("Deals the solver gave up on are counted as lost, so the real chance may be higher");
+    }
+    let chance = match est.chance() {
+        None => {
+            println!("No deal has been won: the solitaire is probably impossible");
+            return Ok(());
+        }
+        Some(n) => n,
+    };
+    println!("Chance of winning: 1 of {}", chance);
+    if cli.save_chance {
+        write_chance(target, chance)?;
+        println!("The chance is saved to {}", target);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod estimate_test {
+    use super::*;
+
+    #[test]
+    fn chance_in_rules() {
+        let text = "[global]\nname = Test\n\n[column]\ncount = 3\n";
+        let res = set_chance(text, 4);
+        assert_eq!(res, "[global]\nchance = 4\nname = Test\n\n[column]\ncount = 3\n");
+        let res = set_chance(&res, 12);
+        assert_eq!(res, "[global]\nchance = 12\nname = Test\n\n[column]\ncount = 3\n");
+        let res = set_chance("[column]\r\ncount = 3\r\n[global]\r\nname=T\r\nCHANCE=1\r\n", 3);
+        assert_eq!(res, "[column]\r\ncount = 3\r\n[global]\r\nname=T\r\nchance = 3\r\n");
+        let res = set_chance("\u{feff}[column]\ncount = 3\n", 5);
+        assert_eq!(res, "\u{feff}[global]\nchance = 5\n[column]\ncount = 3\n");
+    }
+}
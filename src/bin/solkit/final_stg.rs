@@ -8,19 +8,65 @@ use crate::primitive::{Border, Screen};
 use crate::strategy::{Context, Strategy, Transition, TransitionStage};
 use crate::theme::Theme;
 
-const ITEM_COUNT: usize = 4;
 const ITEM_HEIGHT: u16 = 3;
 const MENU_WIDTH: u16 = 28;
-const MENU_ITEMS: [&str; 4] = ["Back to game", "Play again", "Choose solitaire", "Exit application"];
 
-// menu displayed on ESC or when a solitaire is solved
+#[derive(Clone, Copy)]
+enum Action {
+    Back,
+    PlayAgain,
+    Choose,
+    Exit,
+    Undo,
+    Restart,
+}
+
+const MENU_ITEMS: [(&str, Action); 4] = [
+    ("Back to game", Action::Back),
+    ("Play again", Action::PlayAgain),
+    ("Choose solitaire", Action::Choose),
+    ("Exit application", Action::Exit),
+];
+const LOST_ITEMS: [(&str, Action); 4] = [
+    ("Undo last move", Action::Undo),
+    ("Restart deal", Action::Restart),
+    ("New deal", Action::PlayAgain),
+    ("Choose solitaire", Action::Choose),
+];
+
+// menu displayed on ESC or when a solitaire is solved.
+// When no move is left, the menu offers to undo the last move or to start over
 pub(crate) struct FinalStg {
     selected: usize,
+    items: &'static [(&'static str, Action)],
+    lost: bool,
 }
 
 impl FinalStg {
     pub(crate) fn new(_ctx: &mut Context) -> Result<Self, SolError> {
-        Ok(FinalStg { selected: 0 })
+        Ok(FinalStg { selected: 0, items: &MENU_ITEMS, lost: false })
+    }
+
+    pub(crate) fn new_lost(_ctx: &mut Context) -> Result<Self, SolError> {
+        Ok(FinalStg { selected: 0, items: &LOST_ITEMS, lost: true })
+    }
+}
+
+fn act(ctx: &mut Context, action: Action) -> Transition {
+    match action {
+        Action::Back => Transition::Pop,
+        Action::PlayAgain => Transition::Replace(TransitionStage::Play),
+        Action::Choose => Transition::Replace(TransitionStage::Choose),
+        Action::Exit => Transition::Exit,
+        Action::Undo => {
+            ctx.lost = false;
+            ctx.undo = true;
+            Transition::Pop
+        }
+        Action::Restart => {
+            ctx.deal = ctx.saved.as_ref().map(|s| s.seed);
+            Transition::Replace(TransitionStage::Play)
+        }
     }
 }
 
@@ -31,43 +77,28 @@ impl Strategy for FinalStg {
                 KeyCode::Esc => return Ok(Transition::Pop),
                 KeyCode::Up | KeyCode::Char('k') => {
                     if self.selected == 0 {
-                        self.selected = ITEM_COUNT - 1;
+                        self.selected = self.items.len() - 1;
                     } else {
                         self.selected -= 1;
                     }
                 }
                 KeyCode::Down | KeyCode::Char('j') => {
-                    if self.selected == ITEM_COUNT - 1 {
+                    if self.selected == self.items.len() - 1 {
                         self.selected = 0;
                     } else {
                         self.selected += 1;
                     }
                 }
 
-                KeyCode::Enter => {
-                    if self.selected == ITEM_COUNT - 1 {
-                        return Ok(Transition::Exit);
-                    } else if self.selected == 0 {
-                        return Ok(Transition::Pop);
-                    } else if self.selected == 1 {
-                        return Ok(Transition::Replace(TransitionStage::Play));
-                    } else if self.selected == 2 {
-                        return Ok(Transition::Replace(TransitionStage::Choose));
-                    }
-                }
+                KeyCode::Enter => return Ok(act(ctx, self.items[self.selected].1)),
 
                 _ => {}
             },
             Event::Mouse(ev) => {
-                if let MouseEvent::Down(btn, x, y, _) = ev {
-                    if let MouseButton::Left = btn {
-                        match scr.what_at(x, y) {
-                            1 => return Ok(Transition::Pop),
-                            2 => return Ok(Transition::Replace(TransitionStage::Play)),
-                            3 => return Ok(Transition::Replace(TransitionStage::Choose)),
-                            4 => return Ok(Transition::Exit),
-                            _ => {}
-                        }
+                if let MouseEvent::Down(MouseButton::Left, x, y, _) = ev {
+                    let w = usize::from(scr.what_at(x, y));
+                    if w != 0 && w <= self.items.len() {
+                        return Ok(act(ctx, self.items[w - 1].1));
                     }
                 }
             }
@@ -91,13 +122,16 @@ impl Strategy for FinalStg {
 
     fn draw(&self, ctx: &mut Context, scr: &mut Screen, theme: &dyn Theme) -> Result<(), SolError> {
         let x = ctx.w / 2 - MENU_WIDTH / 2;
-        let h = ITEM_COUNT as u16 * ITEM_HEIGHT + 2;
+        let h = self.items.len() as u16 * ITEM_HEIGHT + 2;
         let y = ctx.h / 2 - h / 2;
 
         let (fg, bg) = theme.base_colors();
         scr.colors(fg, bg);
         scr.draw_frame(x, y, MENU_WIDTH, h, Border::Double);
-        for (idx, item) in MENU_ITEMS.iter().enumerate() {
+        if self.lost {
+            scr.write_string(" No more moves ", x + 1, y);
+        }
+        for (idx, (item, _)) in self.items.iter().enumerate() {
             let (fg, bg) = if idx == self.selected { theme.menu_selected_item() } else { theme.base_colors() };
             scr.kind(idx as u16 + 1);
             scr.colors(fg, bg);
@@ -107,11 +141,11 @@ impl Strategy for FinalStg {
             if slen % 2 == 1 {
                 shift -= 1;
             }
-            scr.write_string(MENU_ITEMS[idx], x + shift, y + 2 + idx as u16 * ITEM_HEIGHT);
+            scr.write_string(item, x + shift, y + 2 + idx as u16 * ITEM_HEIGHT);
         }
         Ok(())
     }
 
-    fn on_activate(&self, _ctx: &mut Context) {}
+    fn on_activate(&mut self, _ctx: &mut Context) {}
     fn on_deactivate(&self, _ctx: &mut Context) {}
 }
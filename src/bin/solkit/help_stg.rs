@@ -19,12 +19,12 @@ const DLG_ITEMS: [&str; 21] = [
     "   is already selected, the key acts as `Up`",
     "`Shift`+`1` - `Shift`+`0` (`!` - `(`)  - select a pile of play area.",
     "   If the pile is already selected, the key acts as `Down`",
-    "`d`, `f`, `c` - select first pile in a group if the group exists.",
-    "   If any pile in the group is already selected, the selection ",
-    "   moves to the next pile in the group.",
-    "`s` - `s`how hints: highlight cards that can be played",
-    "`shift`+`s` - `S`how hints: highlight cards where current card can move",
-    "`u` - `u`ndo last move",
+    "`d`, `f`, `c`, `e` - select the first pile of a group, or the next",
+    "   pile of the group if any of its piles is already selected.",
+    "`s` - `s`how playable cards, `S` - where the current card can move",
+    "`u` - `u`ndo last move, `U` or `Ctrl`+`r` - redo the undone move",
+    "`a` - `a`utoplay: move safe cards to foundations, `A` - toggle it",
+    "`shift`+`f`(`F`) - finish a trivially won game, `w` - `w`rite a replay",
     "`shift`+`r`(`R`) or `F5` - `r`edeal: start a new game",
     "`Space` - mark/unmark a card for the next move",
     "`Enter` or `m` - move a marked card to the currently selected one.",
@@ -86,6 +86,6 @@ impl Strategy for HelpStg {
         Ok(())
     }
 
-    fn on_activate(&self, _ctx: &mut Context) {}
+    fn on_activate(&mut self, _ctx: &mut Context) {}
     fn on_deactivate(&self, _ctx: &mut Context) {}
 }
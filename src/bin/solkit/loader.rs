@@ -1,7 +1,10 @@
-use solkit::card::{str_to_face, str_to_suit, Card, Face, Suit};
+use solkit::card::{
+    face_is_rank, str_to_face, str_to_suit, Ace, Card, DeckKind, Face, Suit, MAX_DECK_SUITS, MAX_JOKERS,
+};
 use solkit::err::SolError;
 use solkit::gconf::{
-    str_to_face_order, str_to_suit_order, ColConf, Conf, FaceOrder, FndSlot, PileConf, Playable, SuitOrder, TempConf,
+    pyramid_places, pyramid_rows, str_to_face_order, str_to_suit_order, ColConf, ColPlace, Collect, Conf, DealCols,
+    FaceOrder, FndSlot, Goal, Layout, PileConf, Playable, RedealMode, ReserveConf, SuitOrder, TempConf, TempRefill,
 };
 
 // load a solitaire rules from a UTF-8 text file
@@ -19,10 +22,12 @@ pub(crate) fn load_config(data: &[String]) -> Result<Conf, SolError> {
             "deck" => idx = parse_deck(&mut conf, data, idx + 1)?,
             "foundation" => idx = parse_foundation(&mut conf, data, idx + 1)?,
             "temp" => idx = parse_temp(&mut conf, data, idx + 1)?,
+            "reserve" => idx = parse_reserve(&mut conf, data, idx + 1)?,
             "column" => idx = parse_play(&mut conf, data, idx + 1)?,
             _ => return Err(SolError::InvalidConfSection(section_name.to_string())),
         }
     }
+    conf.validate()?;
     Ok(conf)
 }
 
@@ -47,7 +52,29 @@ fn parse_global(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, S
                 Err(_) => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
             },
             "decks" => match opt_val.parse::<u8>() {
-                Ok(n) if n == 1 || n == 2 => conf.deck_count = n,
+                // the upper limit depends on the number of suits and is checked by Conf::validate
+                Ok(n) if n > 0 && usize::from(n) <= MAX_DECK_SUITS => conf.deck_count = n,
+                _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
+            "ranks" => parse_ranks(&mut conf.deck, opt_name, opt_val)?,
+            "suits" => parse_suits(&mut conf.deck, opt_name, opt_val)?,
+            "jokers" => match opt_val.parse::<u8>() {
+                Ok(n) if n <= MAX_JOKERS => conf.deck.jokers = n,
+                _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
+            "wrap" => match opt_val {
+                "yes" | "true" | "on" => conf.rank_order.wrap = true,
+                "no" | "false" | "off" => conf.rank_order.wrap = false,
+                _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
+            "ace" => match opt_val {
+                "low" => conf.rank_order.ace = Ace::Low,
+                "high" => conf.rank_order.ace = Ace::High,
+                _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
+            "goal" => match opt_val {
+                "all" | "all cards" => conf.goal = Goal::AllCards,
+                "tableau" | "columns" => conf.goal = Goal::Tableau,
                 _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
             },
             _ => return Err(SolError::InvalidConfOption("global".to_string(), opt_name.to_string())),
@@ -56,9 +83,57 @@ fn parse_global(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, S
     Ok(idx)
 }
 
+// ranks of a single deck: "full", "piquet"(or "32"), "36", or a list of faces
+fn parse_ranks(deck: &mut DeckKind, opt_name: &str, opt_val: &str) -> Result<(), SolError> {
+    match opt_val {
+        "full" | "all" | "52" => deck.faces = DeckKind::full().faces,
+        "piquet" | "32" => deck.faces = DeckKind::piquet().faces,
+        "36" => deck.faces = DeckKind::stripped36().faces,
+        _ => {
+            let mut faces = Vec::new();
+            for f in opt_val.split(|c: char| c == ',' || c.is_whitespace()).filter(|f| !f.is_empty()) {
+                match str_to_face(f) {
+                    Ok(face) if face_is_rank(face) => faces.push(face),
+                    _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+                }
+            }
+            deck.set_faces(&faces);
+        }
+    }
+    Ok(())
+}
+
+// suits of a single deck: "all" or a list of suits
+fn parse_suits(deck: &mut DeckKind, opt_name: &str, opt_val: &str) -> Result<(), SolError> {
+    if opt_val == "all" {
+        deck.suits = DeckKind::full().suits;
+        return Ok(());
+    }
+    let mut suits = Vec::new();
+    for s in opt_val.split(|c: char| c == ',' || c.is_whitespace()).filter(|s| !s.is_empty()) {
+        match str_to_suit(s) {
+            Ok(Suit::Any) | Err(_) => {
+                return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string()))
+            }
+            Ok(suit) => suits.push(suit),
+        }
+    }
+    deck.set_suits(&suits);
+    Ok(())
+}
+
 fn parse_deck(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, SolError> {
     let mut idx = idx;
-    let mut pconf = PileConf { deal_by: 0, redeals: 0, pile_to_cols: false };
+    let mut pconf = PileConf {
+        deal_by: 0,
+        redeal_by: 0,
+        redeals: 0,
+        redeal: RedealMode::Turn,
+        pile_to_cols: false,
+        deal_cols: DealCols::All,
+        pile_to_fnd: false,
+        require_filled: false,
+    };
     while idx < data.len() {
         let low = &data[idx];
         idx += 1;
@@ -88,9 +163,26 @@ fn parse_deck(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, Sol
                 Ok(n) => pconf.deal_by = n,
                 _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
             },
+            "redeal_by" => match opt_val.parse::<u8>() {
+                Ok(n) => pconf.redeal_by = n,
+                _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
+            "redeal_mode" => match opt_val {
+                "turn" | "flip" => pconf.redeal = RedealMode::Turn,
+                "keep" | "no reverse" => pconf.redeal = RedealMode::Keep,
+                "shuffle" => pconf.redeal = RedealMode::Shuffle,
+                _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
+            "deal_cols" => pconf.deal_cols = parse_deal_cols(opt_name, opt_val)?,
+            "require_filled" => match opt_val {
+                "yes" | "true" | "on" => pconf.require_filled = true,
+                "no" | "false" | "off" => pconf.require_filled = false,
+                _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
             "deal_to" => match opt_val {
-                "deck" | "side" | "waste" => pconf.pile_to_cols = false,
-                "column" | "columns" => pconf.pile_to_cols = true,
+                "deck" | "side" | "waste" => (pconf.pile_to_cols, pconf.pile_to_fnd) = (false, false),
+                "column" | "columns" => (pconf.pile_to_cols, pconf.pile_to_fnd) = (true, false),
+                "foundation" => (pconf.pile_to_cols, pconf.pile_to_fnd) = (false, true),
                 _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
             },
             _ => return Err(SolError::InvalidConfOption("deck".to_string(), opt_name.to_string())),
@@ -101,6 +193,31 @@ fn parse_deck(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, Sol
     Ok(idx)
 }
 
+// "all", "empty", "single N"(deal_by cards to the column N), or a list of column numbers starting from 1
+fn parse_deal_cols(name: &str, val: &str) -> Result<DealCols, SolError> {
+    let err = || SolError::InvalidConfOptionValue(name.to_string(), val.to_string());
+    let col_num = |s: &str| match s.parse::<u8>() {
+        Ok(n) if (1..=32).contains(&n) => Ok(n - 1),
+        _ => Err(err()),
+    };
+    match val {
+        "all" => return Ok(DealCols::All),
+        "empty" => return Ok(DealCols::Empty),
+        _ => {}
+    }
+    if let Some(n) = val.strip_prefix("single") {
+        return Ok(DealCols::Single(col_num(n.trim())?));
+    }
+    let mut mask = 0u32;
+    for item in val.split(|c: char| c == ',' || c.is_whitespace()).filter(|s| !s.is_empty()) {
+        mask |= 1 << col_num(item)?;
+    }
+    if mask == 0 {
+        return Err(err());
+    }
+    Ok(DealCols::Subset(mask))
+}
+
 fn parse_foundation(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, SolError> {
     let mut idx = idx;
     let mut fnd: Vec<FndSlot> = Vec::new();
@@ -122,9 +239,24 @@ fn parse_foundation(conf: &mut Conf, data: &[String], idx: usize) -> Result<usiz
         let opt_val = low[pos + 1..].trim();
         match opt_name {
             "column" => {
-                let slot = parse_fnd_slot(&opt_val)?;
+                let slot = parse_fnd_slot(opt_val)?;
                 fnd.push(slot);
             }
+            "collect" => match opt_val {
+                "card" | "cards" => conf.collect = Collect::Card,
+                "sequence" | "run" => conf.collect = Collect::Sequence,
+                "pairs" | "same face" => conf.collect = Collect::SameFace,
+                _ => conf.collect = parse_sum(opt_val)?,
+            },
+            "fnd_playable" | "playable" => match opt_val {
+                "yes" | "true" | "on" => conf.fnd_playable = true,
+                "no" | "false" | "off" => conf.fnd_playable = false,
+                _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
+            "fnd_penalty" | "penalty" => match opt_val.parse::<u16>() {
+                Ok(n) => conf.fnd_penalty = n,
+                Err(_) => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
             _ => return Err(SolError::InvalidConfOption("foundation".to_string(), opt_name.to_string())),
         }
     }
@@ -135,6 +267,34 @@ fn parse_foundation(conf: &mut Conf, data: &[String], idx: usize) -> Result<usiz
     Ok(idx)
 }
 
+// "sum N": two cards which values sum to N are removed together
+fn parse_sum(s: &str) -> Result<Collect, SolError> {
+    let v: Vec<&str> = s.split_whitespace().collect();
+    if v.len() == 2 && v[0] == "sum" {
+        if let Ok(n) = v[1].parse::<u8>() {
+            if n > 1 && n <= 26 {
+                return Ok(Collect::Sum(n));
+            }
+        }
+    }
+    Err(SolError::InvalidConfOptionValue("collect".to_string(), s.to_string()))
+}
+
+// "columns" or "grid N" where N is the number of cards in a grid row. A pyramid depends on
+// the number of columns, so it is built after all columns are loaded
+fn parse_layout(s: &str) -> Result<Layout, SolError> {
+    if s == "columns" {
+        return Ok(Layout::Columns);
+    }
+    let v: Vec<&str> = s.split_whitespace().collect();
+    if v.len() == 2 && v[0] == "grid" {
+        if let Ok(n) = v[1].parse::<u8>() {
+            return Ok(Layout::Grid(n));
+        }
+    }
+    Err(SolError::InvalidConfOptionValue("layout".to_string(), s.to_string()))
+}
+
 fn parse_fnd_slot(s: &str) -> Result<FndSlot, SolError> {
     let mut slot = FndSlot {
         first: Face::Any,
@@ -165,16 +325,15 @@ fn parse_fnd_slot(s: &str) -> Result<FndSlot, SolError> {
 
 fn parse_temp(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, SolError> {
     let mut idx = idx;
-    let mut tconf = TempConf { count: 0 };
+    let mut tconf = TempConf::new(0);
+    // by default, slots with dealt cards are reserves and empty slots are free cells
+    let mut refill: Option<TempRefill> = None;
     while idx < data.len() {
         let low = &data[idx];
         idx += 1;
         if low.starts_with('[') {
-            if tconf.count != 0 {
-                tconf.validate()?;
-                conf.temp = Some(tconf);
-            }
-            return Ok(idx - 1);
+            idx -= 1;
+            break;
         }
         let pos = match low.find('=') {
             Some(n) => n,
@@ -187,9 +346,33 @@ fn parse_temp(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, Sol
                 Ok(n) => tconf.count = n,
                 _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
             },
+            "limit" => match opt_val {
+                "unlimited" | "none" => tconf.limit = 0,
+                _ => match opt_val.parse::<u8>() {
+                    Ok(n) => tconf.limit = n,
+                    _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+                },
+            },
+            "order" => {
+                let v: Vec<&str> = opt_val.split(',').collect();
+                if v.len() != 2 {
+                    return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string()));
+                }
+                tconf.order = Some((str_to_face_order(v[0].trim())?, str_to_suit_order(v[1].trim())?));
+            }
+            "deal" | "cards" => match opt_val.parse::<u8>() {
+                Ok(n) => tconf.deal = n,
+                _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
+            "refillable" => refill = Some(parse_temp_refill(opt_val)?),
             _ => return Err(SolError::InvalidConfOption("temp".to_string(), opt_name.to_string())),
         }
     }
+    tconf.refill = match refill {
+        Some(r) => r,
+        None if tconf.deal == 0 => TempRefill::All,
+        None => TempRefill::Nothing,
+    };
     if tconf.count != 0 {
         tconf.validate()?;
         conf.temp = Some(tconf);
@@ -197,13 +380,32 @@ fn parse_temp(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, Sol
     Ok(idx)
 }
 
-fn parse_play(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, SolError> {
+// "all", "none", or the number of the only slot that accepts cards(starting from 1)
+fn parse_temp_refill(s: &str) -> Result<TempRefill, SolError> {
+    match s {
+        "all" | "yes" | "true" => return Ok(TempRefill::All),
+        "none" | "no" | "false" => return Ok(TempRefill::Nothing),
+        _ => {}
+    }
+    let v: Vec<&str> = s.split(',').collect();
+    if v.len() > 1 {
+        return Err(SolError::InvalidConfTempSingleRefillable);
+    }
+    match s.parse::<u8>() {
+        Ok(n) if n != 0 => Ok(TempRefill::Slot(n - 1)),
+        _ => Err(SolError::InvalidConfOptionValue("refillable".to_string(), s.to_string())),
+    }
+}
+
+fn parse_reserve(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, SolError> {
     let mut idx = idx;
+    let mut rconf = ReserveConf { count: 0, up: 1, refill_cols: false };
     while idx < data.len() {
         let low = &data[idx];
         idx += 1;
         if low.starts_with('[') {
-            return Ok(idx - 1);
+            idx -= 1;
+            break;
         }
         let pos = match low.find('=') {
             Some(n) => n,
@@ -212,13 +414,117 @@ fn parse_play(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, Sol
         let opt_name = low[..pos].trim();
         let opt_val = low[pos + 1..].trim();
         match opt_name {
-            "playable_card" => match opt_val {
-                "top" => conf.playable = Playable::Top,
-                "any" => conf.playable = Playable::Any,
-                "order" | "ordered" => conf.playable = Playable::Ordered,
+            "cards" => match opt_val.parse::<u8>() {
+                Ok(n) => rconf.count = n,
+                _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
+            "up" => match opt_val.parse::<u8>() {
+                Ok(n) => rconf.up = n,
+                _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
+            "refill_columns" => match opt_val {
+                "yes" | "true" | "on" => rconf.refill_cols = true,
+                "no" | "false" | "off" => rconf.refill_cols = false,
                 _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
             },
+            _ => return Err(SolError::InvalidConfOption("reserve".to_string(), opt_name.to_string())),
+        }
+    }
+    rconf.validate()?;
+    conf.reserve = Some(rconf);
+    Ok(idx)
+}
+
+// "X, Y" or "X, Y, C1 C2 ..." where C1, C2 etc are the numbers of columns(starting from 1)
+// that cover this one
+fn parse_place(s: &str) -> Result<ColPlace, SolError> {
+    let err = || SolError::InvalidConfOptionValue("place".to_string(), s.to_string());
+    let v: Vec<&str> = s.split(',').collect();
+    if v.len() != 2 && v.len() != 3 {
+        return Err(err());
+    }
+    let x = v[0].trim().parse::<u8>().map_err(|_| err())?;
+    let y = v[1].trim().parse::<u8>().map_err(|_| err())?;
+    let mut covered_by = Vec::new();
+    if v.len() == 3 {
+        for w in v[2].split_whitespace() {
+            match w.parse::<usize>() {
+                Ok(n) if n != 0 => covered_by.push(n - 1),
+                _ => return Err(err()),
+            }
+        }
+    }
+    Ok(ColPlace { x, y, covered_by })
+}
+
+fn str_to_playable(s: &str) -> Option<Playable> {
+    match s {
+        "top" => Some(Playable::Top),
+        "any" => Some(Playable::Any),
+        "order" | "ordered" => Some(Playable::Ordered),
+        "suit" | "suited" | "same suit" => Some(Playable::SameSuit),
+        "supermove" => Some(Playable::Supermove),
+        _ => None,
+    }
+}
+
+// "COUNT, UP" followed by optional items: "take only", and the rules that override the common
+// column ones: "order FACE-ORDER SUIT-ORDER", "refill FACE", and "playable PLAYABLE"
+fn parse_column(s: &str) -> Result<ColConf, SolError> {
+    let err = || SolError::InvalidConfOptionValue("column".to_string(), s.to_string());
+    let v: Vec<&str> = s.split(',').map(|w| w.trim()).collect();
+    if v.len() < 2 {
+        return Err(err());
+    }
+    let count = v[0].parse::<u8>().map_err(|_| err())?;
+    let up = v[1].parse::<u8>().map_err(|_| err())?;
+    let mut col = ColConf::new(count, up);
+    for item in &v[2..] {
+        let (name, val) = match item.split_once(' ') {
+            Some((name, val)) => (name, val.trim()),
+            None => (*item, ""),
+        };
+        match name {
+            "take" | "takeonly" => col.take_only = true,
+            "order" => {
+                let (forder, sorder) = val.split_once(' ').ok_or_else(err)?;
+                col.rules.forder = Some(str_to_face_order(forder)?);
+                col.rules.sorder = Some(str_to_suit_order(sorder.trim())?);
+            }
+            "refill" => col.rules.refill = Some(str_to_face(val)?),
+            "playable" => col.rules.playable = Some(str_to_playable(val).ok_or_else(err)?),
+            _ => return Err(err()),
+        }
+    }
+    Ok(col)
+}
+
+fn parse_play(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, SolError> {
+    let mut idx = idx;
+    let mut pyramid = false;
+    let mut places: Vec<ColPlace> = Vec::new();
+    while idx < data.len() {
+        let low = &data[idx];
+        idx += 1;
+        if low.starts_with('[') {
+            idx -= 1;
+            break;
+        }
+        let pos = match low.find('=') {
+            Some(n) => n,
+            None => return Err(SolError::InvalidConfLine(low.to_string())),
+        };
+        let opt_name = low[..pos].trim();
+        let opt_val = low[pos + 1..].trim();
+        match opt_name {
+            "playable_card" => match str_to_playable(opt_val) {
+                Some(p) => conf.playable = p,
+                None => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
+            },
             "refill" => conf.col_refill = str_to_face(opt_val)?,
+            "layout" if opt_val == "pyramid" => pyramid = true,
+            "layout" => conf.layout = parse_layout(opt_val)?,
+            "place" => places.push(parse_place(opt_val)?),
             "order" => {
                 let v: Vec<&str> = opt_val.split(',').collect();
                 if v.len() != 2 {
@@ -227,34 +533,103 @@ fn parse_play(conf: &mut Conf, data: &[String], idx: usize) -> Result<usize, Sol
                 conf.col_forder = str_to_face_order(v[0].trim())?;
                 conf.col_sorder = str_to_suit_order(v[1].trim())?;
             }
-            "column" => {
-                let v: Vec<&str> = opt_val.split(',').collect();
-                if v.len() != 2 && v.len() != 3 {
-                    return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string()));
-                }
-                let count = match v[0].trim().parse::<u8>() {
-                    Ok(n) => n,
-                    Err(_) => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
-                };
-                let up = match v[1].trim().parse::<u8>() {
-                    Ok(n) => n,
-                    Err(_) => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
-                };
-                let take_only = if v.len() != 3 {
-                    false
-                } else {
-                    match v[2].trim() {
-                        "take" | "takeonly" | "take only" => true,
-                        _ => return Err(SolError::InvalidConfOptionValue(opt_name.to_string(), opt_val.to_string())),
-                    }
-                };
-                conf.cols.push(ColConf { count, up, take_only });
-            }
+            "column" => conf.cols.push(parse_column(opt_val)?),
             _ => return Err(SolError::InvalidConfOption("foundation".to_string(), opt_name.to_string())),
         }
     }
     if conf.cols.is_empty() {
         return Err(SolError::NoCols);
     }
+    if pyramid {
+        match pyramid_rows(conf.cols.len()) {
+            Some(rows) if rows <= 7 => conf.layout = Layout::Free(pyramid_places(rows)),
+            _ => return Err(SolError::InvalidConfPyramid(conf.cols.len())),
+        }
+    } else if !places.is_empty() {
+        conf.layout = Layout::Free(places);
+    }
     Ok(idx)
 }
+
+#[cfg(test)]
+mod loader_test {
+    use super::*;
+    use solkit::gconf::ColRules;
+
+    fn temp_conf(lines: &[&str]) -> Result<Option<TempConf>, SolError> {
+        let data: Vec<String> = lines.iter().map(|s| s.to_string()).collect();
+        let mut conf = Conf::new();
+        parse_temp(&mut conf, &data, 0)?;
+        Ok(conf.temp)
+    }
+
+    #[test]
+    fn temp_options() {
+        let tc = temp_conf(&["slots = 4"]).unwrap().unwrap();
+        assert!(tc.limit == 1 && tc.refill == TempRefill::All);
+        let tc = temp_conf(&["slots = 3", "deal = 4", "limit = 6", "order = desc, alternate", "refillable = 3"])
+            .unwrap()
+            .unwrap();
+        assert!(tc.deal == 4 && tc.limit == 6 && tc.refill == TempRefill::Slot(2));
+        let tc = temp_conf(&["slots = 2", "deal = 5", "limit = unlimited"]).unwrap().unwrap();
+        assert!(tc.refill == TempRefill::Nothing);
+
+        assert!(matches!(temp_conf(&["slots = 2", "limit = 0"]), Err(SolError::InvalidConfTempLimit)));
+        assert!(matches!(temp_conf(&["slots = 2", "limit = 3"]), Err(SolError::InvalidConfTempOrder)));
+        assert!(matches!(
+            temp_conf(&["slots = 2", "deal = 3", "limit = 2", "order = desc, any", "refillable = 1"]),
+            Err(SolError::InvalidConfTempDeal(3, 2))
+        ));
+        assert!(matches!(
+            temp_conf(&["slots = 2", "deal = 1", "refillable = 1, 2"]),
+            Err(SolError::InvalidConfTempSingleRefillable)
+        ));
+        assert!(temp_conf(&["slots = 2", "refillable = 3"]).is_err());
+    }
+
+    #[test]
+    fn pairing_options() {
+        assert!(parse_sum("sum 13").unwrap() == Collect::Sum(13));
+        assert!(parse_sum("sum").is_err() && parse_sum("sum 40").is_err());
+        assert!(parse_layout("grid 5").unwrap() == Layout::Grid(5));
+        assert!(parse_layout("grid").is_err());
+        let place = parse_place("3, 1, 5 6").unwrap();
+        assert!(place == ColPlace { x: 3, y: 1, covered_by: vec![4, 5] });
+        assert!(parse_place("3, 1, 0").is_err() && parse_place("3").is_err());
+    }
+
+    #[test]
+    fn column_options() {
+        let col = parse_column("5, 1").unwrap();
+        assert!(col.count == 5 && col.up == 1 && !col.take_only && col.rules == ColRules::default());
+        let col = parse_column("3, 3, take only, order desc same suit, refill k, playable top").unwrap();
+        assert!(col.take_only && col.rules.playable == Some(Playable::Top));
+        assert!(col.rules.forder == Some(FaceOrder::Desc) && col.rules.sorder == Some(SuitOrder::SameSuit));
+        assert!(col.rules.refill == Some(Face::K));
+        assert!(parse_column("3, 3, order desc").is_err());
+        assert!(parse_column("3, 3, playable never").is_err());
+        assert!(parse_column("3").is_err());
+    }
+
+    #[test]
+    fn deck_options() {
+        let mut deck = DeckKind::full();
+        parse_ranks(&mut deck, "ranks", "36").unwrap();
+        assert_eq!(deck.size(), 36);
+        parse_ranks(&mut deck, "ranks", "k, q j 10 9 8 7 a").unwrap();
+        assert!(deck == DeckKind::piquet());
+        assert!(parse_ranks(&mut deck, "ranks", "7 any").is_err());
+        parse_suits(&mut deck, "suits", "hearts spades").unwrap();
+        assert!(deck.suits == vec![Suit::Spade, Suit::Heart]);
+        assert!(parse_suits(&mut deck, "suits", "any").is_err());
+    }
+
+    #[test]
+    fn deal_cols_option() {
+        assert_eq!(parse_deal_cols("deal_cols", "empty").unwrap(), DealCols::Empty);
+        assert_eq!(parse_deal_cols("deal_cols", "single 8").unwrap(), DealCols::Single(7));
+        assert_eq!(parse_deal_cols("deal_cols", "1, 2 3").unwrap(), DealCols::Subset(0b111));
+        assert!(parse_deal_cols("deal_cols", "0").is_err());
+        assert!(parse_deal_cols("deal_cols", "single").is_err());
+    }
+}
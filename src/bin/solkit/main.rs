@@ -6,6 +6,7 @@ extern crate log;
 mod buffer;
 mod choose_stg;
 mod config;
+mod estimate;
 mod final_stg;
 mod gstate;
 mod help_stg;
@@ -13,14 +14,18 @@ mod loader;
 mod opts;
 mod play_stg;
 mod primitive;
+mod replay_stg;
+mod resume_stg;
 mod rules;
+mod saves;
+mod solve;
 mod stats;
 mod strategy;
 mod theme;
 mod ui;
 mod userconf;
 
-use std::fs::File;
+use std::fs::{read_to_string, File};
 use std::io::{stdin, stdout, Write};
 
 use anyhow::{anyhow, Result};
@@ -33,11 +38,17 @@ use crossterm::{
 };
 use simplelog::*;
 
+use solkit::err::SolError;
+use solkit::replay::Replay;
+
 use choose_stg::ChooseStg;
 use final_stg::FinalStg;
 use help_stg::HelpStg;
 use play_stg::PlayStg;
 use primitive::Screen;
+use replay_stg::ReplayStg;
+use resume_stg::ResumeStg;
+use saves::SavedSession;
 use strategy::{Context, Strategy, Transition, TransitionStage};
 
 fn scr_reset(scr: &mut Screen) {
@@ -73,8 +84,24 @@ fn main_loop(cli: &opts::CliOpts) -> Result<()> {
     }
     ctx.name = user_conf.last_played.clone();
     ctx.custom = !cli.filename.is_empty();
-
-    let mut stg: Box<dyn Strategy> = Box::new(ChooseStg::new(&rules, &mut ctx)?);
+    ctx.deal = cli.deal;
+    ctx.autoplay = user_conf.autoplay;
+    ctx.undo_limit = user_conf.undo_limit;
+
+    let mut stg: Box<dyn Strategy> = match cli.replay {
+        None => Box::new(ChooseStg::new(&rules, &mut ctx)?),
+        Some(ref path) => {
+            let replay = Replay::parse(&read_to_string(path)?)?;
+            let low = replay.name.to_lowercase();
+            let conf = match rules.iter().find(|(name, _)| name.to_lowercase() == low) {
+                None => return Err(SolError::SolitaireNotExist(replay.name).into()),
+                Some((_, conf)) => conf,
+            };
+            // watching a replay changes neither statistics nor settings
+            ctx.custom = true;
+            Box::new(ReplayStg::new(conf, replay)?)
+        }
+    };
     let mut stages: Vec<Box<dyn Strategy>> = Vec::new();
 
     let dark = theme::DarkTheme::new(!cli.four_color);
@@ -103,14 +130,21 @@ fn main_loop(cli: &opts::CliOpts) -> Result<()> {
             },
             Transition::Exit => {
                 stg.on_deactivate(&mut ctx);
-                if ctx.moved {
-                    ctx.stats.update_stat(&ctx.name, ctx.won, ctx.elapsed);
+                let unfinished =
+                    if ctx.moved && !ctx.won && !ctx.lost && !ctx.custom { ctx.saved.take() } else { None };
+                if let Some(game) = unfinished {
+                    // the game is not counted in statistics until it is finished or abandoned
+                    ctx.saves.put(&ctx.name, SavedSession { elapsed: ctx.elapsed, game });
+                    ctx.saves.save();
+                } else if ctx.moved {
+                    ctx.stats.update_stat(&ctx.name, ctx.won, ctx.lost, ctx.elapsed);
                     if !ctx.custom {
                         ctx.stats.save();
                     }
                 }
                 stages.clear();
                 user_conf.last_played = ctx.name.clone();
+                user_conf.autoplay = ctx.autoplay;
                 if !ctx.custom {
                     user_conf.save();
                 }
@@ -133,6 +167,8 @@ fn main_loop(cli: &opts::CliOpts) -> Result<()> {
                         Box::new(ChooseStg::new(&rules, &mut ctx)?)
                     }
                     TransitionStage::HelpDialog => Box::new(HelpStg::new(&mut ctx)?),
+                    TransitionStage::ResumeDialog => Box::new(ResumeStg::new(&mut ctx)?),
+                    TransitionStage::LostDialog => Box::new(FinalStg::new_lost(&mut ctx)?),
                 };
                 stg.on_activate(&mut ctx);
             }
@@ -140,13 +176,16 @@ fn main_loop(cli: &opts::CliOpts) -> Result<()> {
                 stg.on_deactivate(&mut ctx);
                 stages.clear();
                 if ctx.moved {
-                    ctx.stats.update_stat(&ctx.name, ctx.won, ctx.elapsed);
+                    ctx.stats.update_stat(&ctx.name, ctx.won, ctx.lost, ctx.elapsed);
                     if !ctx.custom {
                         ctx.stats.save();
                     }
                 }
                 ctx.moved = false;
                 ctx.won = false;
+                ctx.lost = false;
+                ctx.saved = None;
+                ctx.reset();
                 scr_reset(&mut scr);
                 stg = match st {
                     TransitionStage::EndDialog => Box::new(FinalStg::new(&mut ctx)?),
@@ -162,7 +201,6 @@ fn main_loop(cli: &opts::CliOpts) -> Result<()> {
                     }
                     _ => panic!("unimplemented"),
                 };
-                ctx.reset();
             }
         }
     }
@@ -176,6 +214,13 @@ fn main() -> Result<()> {
         CombinedLogger::init(vec![WriteLogger::new(LevelFilter::Info, cb, File::create("app.log").unwrap())]).unwrap();
     }
 
+    if let Some(ref target) = cli.estimate {
+        return estimate::run(&cli, target);
+    }
+    if let Some(ref target) = cli.solve {
+        return solve::run(&cli, target);
+    }
+
     let mut stdout = stdout();
     execute!(stdout, terminal::EnterAlternateScreen)?;
     enable_raw_mode()?;
@@ -11,17 +11,41 @@ pub(crate) struct CliOpts {
     pub(crate) filename: String,
     pub(crate) logging: bool,
     pub(crate) four_color: bool,
+    pub(crate) deal: Option<u64>,
+    pub(crate) solve: Option<String>, // solitaire name or rules file to solve without UI
+    pub(crate) nodes: Option<usize>,
+    pub(crate) time: Option<u64>,
+    pub(crate) estimate: Option<String>, // solitaire name or rules file to estimate the chance of winning
+    pub(crate) deals: Option<u64>,
+    pub(crate) save_chance: bool,
+    pub(crate) replay: Option<String>, // replay file to play back
 }
 
 fn print_usage(program: &str, opts: &Options) {
-    let brief = format!("Usage: {} [options] [path-to-custom-solitaire-rules]", program);
+    let brief = format!(
+        "Usage: {0} [options] [path-to-custom-solitaire-rules]\n       {0} solve [options] solitaire-name|path-to-rules",
+        program
+    );
     print!("{}", opts.usage(&brief));
 }
 
 pub(crate) fn parse_args() -> CliOpts {
     let args: Vec<String> = env::args().collect();
     let program = args[0].clone();
-    let mut cli = CliOpts { dark: true, filename: String::new(), logging: false, four_color: false };
+    let mut cli = CliOpts {
+        dark: true,
+        filename: String::new(),
+        logging: false,
+        four_color: false,
+        deal: None,
+        solve: None,
+        nodes: None,
+        time: None,
+        estimate: None,
+        deals: None,
+        save_chance: false,
+        replay: None,
+    };
 
     let mut opts = Options::new();
     opts.optflag("h", "help", "Show this help");
@@ -29,6 +53,13 @@ pub(crate) fn parse_args() -> CliOpts {
     opts.optflag("4", "four-color", "Use distinct color for each suit");
     opts.optflag("v", "version", "Show application version");
     opts.optflag("", "log", "Enable logging");
+    opts.optopt("", "deal", "Start the first game with the given deal number", "NUMBER");
+    opts.optopt("", "nodes", "Solver: maximum number of positions to check(0 - unlimited)", "NUMBER");
+    opts.optopt("", "time", "Solver: maximum search time in seconds(0 - unlimited)", "SECONDS");
+    opts.optopt("", "estimate-chance", "Estimate the chance of winning a solitaire", "NAME | FILE");
+    opts.optopt("", "deals", "Number of deals to solve to estimate the chance of winning", "NUMBER");
+    opts.optflag("", "save-chance", "Write the estimated chance of winning to the rules file");
+    opts.optopt("", "replay", "Play back a saved game", "FILE");
 
     let matches: Matches = match opts.parse(&args[1..]) {
         Ok(m) => m,
@@ -63,7 +94,62 @@ pub(crate) fn parse_args() -> CliOpts {
         };
     }
 
-    if !matches.free.is_empty() {
+    if let Some(val) = matches.opt_str("deal") {
+        match val.parse::<u64>() {
+            Ok(n) => cli.deal = Some(n),
+            Err(_) => {
+                eprintln!("Invalid deal number: {}", val);
+                print_usage(&program, &opts);
+                exit(0);
+            }
+        }
+    }
+
+    if let Some(val) = matches.opt_str("nodes") {
+        match val.parse::<usize>() {
+            Ok(n) => cli.nodes = Some(n),
+            Err(_) => {
+                eprintln!("Invalid number of positions: {}", val);
+                print_usage(&program, &opts);
+                exit(0);
+            }
+        }
+    }
+
+    if let Some(val) = matches.opt_str("time") {
+        match val.parse::<u64>() {
+            Ok(n) => cli.time = Some(n),
+            Err(_) => {
+                eprintln!("Invalid search time: {}", val);
+                print_usage(&program, &opts);
+                exit(0);
+            }
+        }
+    }
+
+    if let Some(val) = matches.opt_str("deals") {
+        match val.parse::<u64>() {
+            Ok(n) if n != 0 => cli.deals = Some(n),
+            _ => {
+                eprintln!("Invalid number of deals: {}", val);
+                print_usage(&program, &opts);
+                exit(0);
+            }
+        }
+    }
+
+    cli.estimate = matches.opt_str("estimate-chance");
+    cli.save_chance = matches.opt_present("save-chance");
+    cli.replay = matches.opt_str("replay");
+
+    if !matches.free.is_empty() && matches.free[0] == "solve" {
+        if matches.free.len() < 2 {
+            eprintln!("Solitaire to solve is not specified");
+            print_usage(&program, &opts);
+            exit(0);
+        }
+        cli.solve = Some(matches.free[1].to_string());
+    } else if !matches.free.is_empty() {
         cli.filename = matches.free[0].to_string();
     }
 
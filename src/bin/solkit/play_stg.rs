@@ -1,13 +1,17 @@
 use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
 
 use crossterm::event::{Event, KeyCode, KeyModifiers, MouseButton, MouseEvent};
 use crossterm::terminal;
 
-use solkit::card::{Card, Suit};
-use solkit::engine::{Direction, Game, Pos};
+use solkit::card::{Ace, Card, Suit};
+use solkit::engine::{DealBlock, Direction, Game, Pos};
 use solkit::err::SolError;
 use solkit::gconf::{self, FaceOrder, Playable, SuitOrder};
+use solkit::replay::Replay;
 
+use crate::config::replays_path;
 use crate::primitive::{Border, Screen};
 use crate::strategy::{Context, Strategy, Transition, TransitionStage};
 use crate::theme::Theme;
@@ -18,6 +22,7 @@ const FRC_STR: &str = "free cell";
 const DCK_STR: &str = "deck";
 const WST_STR: &str = "waste";
 const COL_STR: &str = "column";
+const RSV_STR: &str = "reserve";
 
 fn col_to_string(game: &Game, col: usize) -> &'static str {
     let cnt = game.pile_count();
@@ -30,6 +35,9 @@ fn col_to_string(game: &Game, col: usize) -> &'static str {
             return WST_STR;
         }
     }
+    if game.first_reserve() == Some(col) {
+        return RSV_STR;
+    }
     let cnt = game.temp_count();
     if cnt != 0 {
         let first = game.first_temp().expect("first temp must exist");
@@ -65,19 +73,63 @@ fn slot_is_playable(game: &Game, col: usize) -> bool {
     false
 }
 
+// write all moves of a game to a file in the replay directory. Returns the file name
+fn save_replay(game: &Game) -> Result<PathBuf, std::io::Error> {
+    let name: String =
+        game.rules_name().to_lowercase().chars().map(|c| if c.is_alphanumeric() { c } else { '_' }).collect();
+    let mut path = replays_path();
+    path.push(format!("{}-{}.txt", name, game.deal_number()));
+    fs::write(&path, Replay::from_game(game).to_text())?;
+    Ok(path)
+}
+
 // main "dialog" - playing a solitaire
-pub(crate) struct PlayStg<'a> {
-    game: Game<'a>,
+pub(crate) struct PlayStg {
+    game: Game,
+    msg: String, // a message displayed until the next key press
 }
 
-impl<'a> PlayStg<'a> {
-    pub(crate) fn new(rules: &'a HashMap<String, gconf::Conf>, ctx: &mut Context) -> Result<Self, SolError> {
+impl PlayStg {
+    pub(crate) fn new(rules: &HashMap<String, gconf::Conf>, ctx: &mut Context) -> Result<Self, SolError> {
         let gc = match rules.get(&ctx.name) {
             None => return Err(SolError::SolitaireNotExist(ctx.name.to_string())),
             Some(rule) => rule,
         };
-        let game = Game::init(gc)?;
-        Ok(PlayStg { game })
+        // a new game always replaces the saved one
+        let session = ctx.saves.take(&ctx.name);
+        if session.is_some() && !ctx.custom {
+            ctx.saves.save();
+        }
+        let deal = ctx.deal.take();
+        let restored = match session {
+            Some(s) if ctx.resume => match Game::restore(gc.clone(), &s.game) {
+                Ok(game) => {
+                    ctx.elapsed = s.elapsed;
+                    ctx.moved = true;
+                    Some(game)
+                }
+                Err(e) => {
+                    warn!("Failed to restore saved game: {:?}", e);
+                    None
+                }
+            },
+            Some(s) => {
+                // the player starts a new game, so the saved one counts as abandoned
+                if !ctx.custom {
+                    ctx.stats.update_stat(&ctx.name, false, false, s.elapsed);
+                    ctx.stats.save();
+                }
+                None
+            }
+            None => None,
+        };
+        ctx.resume = false;
+        let mut game = match restored {
+            Some(game) => game,
+            None => Game::init(gc.clone(), deal)?,
+        };
+        game.set_undo_limit(ctx.undo_limit);
+        Ok(PlayStg { game, msg: String::new() })
     }
 
     fn draw_stats(&self, ctx: &mut Context, scr: &mut Screen, theme: &dyn Theme) {
@@ -92,15 +144,31 @@ impl<'a> PlayStg<'a> {
         let stats = ctx.stats.game_stat(&ctx.name);
         let played = if ctx.moved { stats.played + 1 } else { stats.played };
         let won = if ctx.won { stats.won + 1 } else { stats.won };
+        let lost = if ctx.lost { stats.lost + 1 } else { stats.lost };
+        let abandoned = played.saturating_sub(won + lost);
         let prc = if played == 0 { 0.0f32 } else { won as f32 / played as f32 };
         let msg = format!("{:7}{:>7}", "Played:", played);
         scr.write_string(&msg, x, y);
         y += 1;
-        let msg = format!("{:7}{:>7}", "Won:", won);
+        // won and lost games share a line to keep the panel above the message row on a short terminal
+        let msg = format!("{:9}{:>5}", "Won/Lost:", format!("{}/{}", won, lost));
+        scr.write_string(&msg, x, y);
+        y += 1;
+        let msg = format!("{:10}{:>4}", "Abandoned:", abandoned);
         scr.write_string(&msg, x, y);
         y += 1;
         let msg = format!("{:7}{:>7.1}", "%", prc);
         scr.write_string(&msg, x, y);
+        y += 1;
+        let msg = format!("{:5}{:>9}", "Deal:", self.game.deal_number());
+        scr.write_string(&msg, x, y);
+        y += 1;
+        let msg = format!("{:6}{:>8}", "Score:", self.game.score());
+        scr.write_string(&msg, x, y);
+        y += 1;
+        let msg = format!("{:9}{:>5}", "Autoplay:", if ctx.autoplay { "on" } else { "off" });
+        scr.write_string(&msg, x, y);
+        y += 1;
         if self.game.pile_count() == 2 {
             if self.game.redeal_left() >= 0 {
                 let msg = format!("{:8}{:>6}", "Redeals:", self.game.redeal_left());
@@ -111,6 +179,12 @@ impl<'a> PlayStg<'a> {
             }
             y += 1;
         }
+        let order = self.game.rank_order();
+        let msg = format!("{:4}{:>10}", "Ace:", if order.ace == Ace::High { "high" } else { "low" });
+        scr.write_string(&msg, x, y);
+        y += 1;
+        let msg = format!("{:5}{:>9}", "Wrap:", if order.wrap { "K-A" } else { "no" });
+        scr.write_string(&msg, x, y);
         y += 2;
 
         let sloc = self.game.selected_loc();
@@ -125,8 +199,12 @@ impl<'a> PlayStg<'a> {
         y += 1;
         let is_playable = slot_is_playable(&self.game, sloc.col);
         let cfg = self.game.slot_conf(sloc.col).expect("pile must have configuration");
+        let high = self.game.rank_order().ace == Ace::High;
         let fsort = if is_playable && cfg.suit_order != SuitOrder::Forbid && !cfg.take_only {
             match cfg.face_order {
+                FaceOrder::Asc if high => "J Q K A",
+                FaceOrder::Desc if high => "A K Q J",
+                FaceOrder::Any if high => "Q K A K",
                 FaceOrder::Asc => "A 2 3 4",
                 FaceOrder::Desc => "4 3 2 A",
                 FaceOrder::Any => "A 2 3 2",
@@ -161,6 +239,8 @@ impl<'a> PlayStg<'a> {
             Playable::Top => "top only",
             Playable::Any => "any group",
             Playable::Ordered => "ordered group",
+            Playable::SameSuit => "suited group",
+            Playable::Supermove => "ordered group(limited)",
         };
         let splay = format!("playble: {}", splayble);
         scr.write_string(&splay, x, y);
@@ -174,7 +254,7 @@ impl<'a> PlayStg<'a> {
             scr.write_string("-", x + markcardtext.len() as u16 + 1, y);
         } else {
             let fc = face_to_str(markcard.face);
-            scr.write_string(&fc, x + markcardtext.len() as u16 + 1, y);
+            scr.write_string(fc, x + markcardtext.len() as u16 + 1, y);
             draw_suit(markcard.suit, scr, x + markcardtext.len() as u16 + 2 + fc.len() as u16, y, theme)
         }
         y += 1;
@@ -187,7 +267,7 @@ impl<'a> PlayStg<'a> {
         } else {
             let fc = face_to_str(selcard.face);
             let shift = x + selcardtext.len() as u16 + 1;
-            scr.write_string(&fc, shift, y);
+            scr.write_string(fc, shift, y);
             draw_suit(selcard.suit, scr, shift + fc.len() as u16 + 1, y, theme);
             if sloc == markcardpos {
                 scr.write_string("[MARKED]", shift + fc.len() as u16 + 3, y);
@@ -216,24 +296,69 @@ fn on_enter(pstg: &mut PlayStg, ctx: &mut Context, curr: Pos) {
         pstg.game.select(Pos { col: pstg.game.selected_loc().col, row: 0 });
         ctx.state.clear_mark();
     }
-    if pstg.game.is_completed() {
-        pstg.game.clear_undo();
-        ctx.won = true;
-    } else {
-        pstg.game.squash_snapshots();
+    pstg.game.squash_snapshots();
+    if ctx.autoplay {
+        on_autoplay(pstg, ctx);
     }
+    check_win(pstg, ctx);
 }
 
 fn on_deal(pstg: &mut PlayStg, ctx: &mut Context) {
     ctx.moved = true;
     pstg.game.take_snapshot();
     ctx.state.clear_mark();
+    match pstg.game.deal_block() {
+        Some(DealBlock::EmptyCol) => pstg.msg = "Fill all empty columns before dealing".to_string(),
+        Some(DealBlock::NoTarget) => pstg.msg = "No column can get a card".to_string(),
+        None => {}
+    }
     pstg.game.deal();
     pstg.game.squash_snapshots();
+    if ctx.autoplay {
+        on_autoplay(pstg, ctx);
+    }
+    // dealing may complete the last run that goes to a foundation
+    check_win(pstg, ctx);
+}
+
+// move all safe cards to foundations
+fn on_autoplay(pstg: &mut PlayStg, ctx: &mut Context) {
+    if pstg.game.autoplay() == 0 {
+        return;
+    }
+    ctx.moved = true;
+    ctx.state.clear_mark();
+    ctx.state.clear_hints();
+    pstg.game.select(Pos { col: pstg.game.selected_loc().col, row: 0 });
+}
+
+// finish a trivially won game
+fn on_autocomplete(pstg: &mut PlayStg, ctx: &mut Context) {
+    if !pstg.game.can_autocomplete() {
+        return;
+    }
+    pstg.game.take_snapshot();
+    ctx.state.clear_mark();
+    ctx.state.clear_hints();
+    if pstg.game.autocomplete() {
+        ctx.moved = true;
+    }
+    check_win(pstg, ctx);
+}
+
+fn check_win(pstg: &mut PlayStg, ctx: &mut Context) {
+    if pstg.game.is_completed() {
+        pstg.game.clear_undo();
+        ctx.won = true;
+    }
 }
 
-impl<'a> Strategy for PlayStg<'a> {
+impl Strategy for PlayStg {
     fn process_event(&mut self, ctx: &mut Context, scr: &mut Screen, event: Event) -> Result<Transition, SolError> {
+        self.game.set_clock(ctx.play_time());
+        if let Event::Key(_) = event {
+            self.msg.clear();
+        }
         match event {
             Event::Key(ev) => match ev.code {
                 KeyCode::Esc => {
@@ -327,6 +452,9 @@ impl<'a> Strategy for PlayStg<'a> {
                 KeyCode::Char('d') => {
                     let _changed = self.game.move_selection(Direction::Pile);
                 }
+                KeyCode::Char('e') => {
+                    let _changed = self.game.move_selection(Direction::Reserve);
+                }
 
                 KeyCode::Char(' ') => {
                     if ctx.won {
@@ -361,9 +489,47 @@ impl<'a> Strategy for PlayStg<'a> {
                 }
 
                 KeyCode::Char('u') => {
+                    ctx.lost = false;
                     self.game.undo();
                 }
 
+                KeyCode::Char('a') => {
+                    if ctx.won {
+                        return Ok(Transition::None);
+                    }
+                    on_autoplay(self, ctx);
+                    check_win(self, ctx);
+                }
+                KeyCode::Char('A') => {
+                    ctx.autoplay = !ctx.autoplay;
+                }
+                KeyCode::Char('F') => {
+                    if ctx.won {
+                        return Ok(Transition::None);
+                    }
+                    on_autocomplete(self, ctx);
+                }
+
+                KeyCode::Char('w') => {
+                    self.msg = match save_replay(&self.game) {
+                        Ok(path) => match path.file_name() {
+                            Some(name) => format!("Replay saved to `{}`", name.to_string_lossy()),
+                            None => "Replay saved".to_string(),
+                        },
+                        Err(e) => {
+                            warn!("Failed to save replay: {:?}", e);
+                            "Failed to save replay".to_string()
+                        }
+                    };
+                }
+
+                KeyCode::Char('U') => {
+                    self.game.redo();
+                }
+                KeyCode::Char('r') if ev.modifiers == KeyModifiers::CONTROL => {
+                    self.game.redo();
+                }
+
                 _ => {}
             },
             Event::Mouse(ev) => {
@@ -417,6 +583,10 @@ impl<'a> Strategy for PlayStg<'a> {
                 ctx.h = height;
             }
         }
+        if ctx.moved && !ctx.won && !ctx.lost && self.game.is_dead_end() {
+            ctx.lost = true;
+            return Ok(Transition::Push(TransitionStage::LostDialog));
+        }
         Ok(Transition::None)
     }
 
@@ -427,6 +597,19 @@ impl<'a> Strategy for PlayStg<'a> {
         draw_area(scr, &self.game, &ctx.state, theme)?;
         self.draw_stats(ctx, scr, theme);
 
+        if !self.msg.is_empty() {
+            let (fg, _bg) = theme.win_msg();
+            scr.write_string_highlight(&self.msg, area_width(&self.game) + 2, ctx.h - 2, fg);
+        } else if !ctx.won && self.game.can_autocomplete() {
+            const FINISH_MSG: &str = "Press `F` to finish the game";
+            let (fg, _bg) = theme.win_msg();
+            scr.write_string_highlight(FINISH_MSG, area_width(&self.game) + 2, ctx.h - 2, fg);
+        } else if ctx.lost {
+            const LOST_MSG: &str = "No more moves. Press `u` to undo";
+            let (fg, _bg) = theme.win_msg();
+            scr.write_string_highlight(LOST_MSG, area_width(&self.game) + 2, ctx.h - 2, fg);
+        }
+
         if ctx.won {
             const VICTORY_MSG: &str = "You win!";
             let vlen = VICTORY_MSG.len() as u16;
@@ -443,11 +626,16 @@ impl<'a> Strategy for PlayStg<'a> {
         Ok(())
     }
 
-    fn on_activate(&self, ctx: &mut Context) {
+    fn on_activate(&mut self, ctx: &mut Context) {
         ctx.unpause();
+        if ctx.undo {
+            ctx.undo = false;
+            self.game.undo();
+        }
     }
 
     fn on_deactivate(&self, ctx: &mut Context) {
         ctx.pause();
+        ctx.saved = Some(self.game.save());
     }
 }
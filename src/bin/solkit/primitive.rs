@@ -234,6 +234,7 @@ impl Screen {
         Ok(())
     }
 
+    // what is displayed at the position. If a few things overlap, it is the one drawn last
     pub(crate) fn what_at(&self, col: u16, row: u16) -> u16 {
         self.buf.what_at(col, row)
     }
@@ -309,5 +310,14 @@ mod buf_test {
             let what = scr.what_at(kw.x, kw.y);
             assert_eq!(what, kw.w);
         }
+
+        // a card covering the lower part of another one
+        scr.kind(4);
+        scr.fill_rect(30, 5, 6, 5, ' ');
+        scr.kind(5);
+        scr.fill_rect(33, 7, 6, 5, ' ');
+        assert_eq!(scr.what_at(33, 6), 4);
+        assert_eq!(scr.what_at(33, 7), 5);
+        assert_eq!(scr.what_at(31, 9), 4);
     }
 }
@@ -0,0 +1,131 @@
+use crossterm::event::{Event, KeyCode};
+use crossterm::terminal;
+
+use solkit::engine::Game;
+use solkit::err::SolError;
+use solkit::gconf::Conf;
+use solkit::replay::Replay;
+
+use crate::primitive::Screen;
+use crate::solve::describe_move;
+use crate::stats::duration_to_human;
+use crate::strategy::{Context, Strategy, Transition};
+use crate::theme::Theme;
+use crate::ui::{area_width, draw_area};
+
+const KEYS_HELP: [&str; 4] =
+    ["`Right` or `l` - next move", "`Left` or `h` - previous move", "`Home`/`End` - first/last move", "`Esc` - exit"];
+
+// step forward and backward through a recorded game
+pub(crate) struct ReplayStg {
+    game: Game,
+    replay: Replay,
+    pos: usize, // the number of moves made
+}
+
+impl ReplayStg {
+    pub(crate) fn new(conf: &Conf, replay: Replay) -> Result<Self, SolError> {
+        replay.validate(conf)?;
+        let game = Game::init(conf.clone(), Some(replay.seed))?;
+        Ok(ReplayStg { game, replay, pos: 0 })
+    }
+
+    fn forward(&mut self) -> Result<(), SolError> {
+        if self.pos < self.replay.moves.len() {
+            self.game.apply(self.replay.moves[self.pos].mv)?;
+            self.pos += 1;
+        }
+        Ok(())
+    }
+
+    fn back(&mut self) {
+        if self.pos != 0 {
+            self.game.undo();
+            self.pos -= 1;
+        }
+    }
+
+    fn draw_info(&self, scr: &mut Screen, theme: &dyn Theme) {
+        let (fg, bg) = theme.base_colors();
+        let x = area_width(&self.game) + 2;
+        let mut y = 1;
+        scr.colors(fg, bg);
+        scr.write_string(&format!("Replay: {}", self.game.rules_name()), x, y);
+        y += 2;
+        scr.write_string(&format!("{:5}{:>9}", "Deal:", self.replay.seed), x, y);
+        y += 1;
+        scr.write_string(&format!("Move: {} of {}", self.pos, self.replay.moves.len()), x, y);
+        y += 1;
+        if self.pos != 0 {
+            let tm = self.replay.moves[self.pos - 1].time;
+            let tm = if tm.as_secs() == 0 { "0m0s".to_string() } else { duration_to_human(Some(tm)) };
+            scr.write_string(&format!("Time: {}", tm), x, y);
+        }
+        y += 2;
+        let next = match self.replay.moves.get(self.pos) {
+            Some(entry) => describe_move(&self.game, entry.mv),
+            None if self.game.is_completed() => "-(game won)".to_string(),
+            None => "-".to_string(),
+        };
+        scr.write_string("Next move:", x, y);
+        y += 1;
+        scr.write_string(&next, x, y);
+        y += 2;
+        let (hfg, _hbg) = theme.win_msg();
+        for line in KEYS_HELP.iter() {
+            scr.write_string_highlight(line, x, y, hfg);
+            y += 1;
+        }
+    }
+}
+
+impl Strategy for ReplayStg {
+    fn process_event(&mut self, ctx: &mut Context, scr: &mut Screen, event: Event) -> Result<Transition, SolError> {
+        match event {
+            Event::Key(ev) => match ev.code {
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(Transition::Exit),
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') | KeyCode::Enter => self.forward()?,
+                KeyCode::Left | KeyCode::Char('h') | KeyCode::Backspace => self.back(),
+                KeyCode::Home => {
+                    while self.pos != 0 {
+                        self.back();
+                    }
+                }
+                KeyCode::End => {
+                    while self.pos < self.replay.moves.len() {
+                        self.forward()?;
+                    }
+                }
+                _ => {}
+            },
+            Event::Mouse(_) => {}
+            Event::Resize(_, _) => {
+                let (width, height) = match terminal::size() {
+                    Err(e) => return Err(SolError::Unexpected(format!("{:?}", e))),
+                    Ok((ww, hh)) => (ww, hh),
+                };
+                if width < 60 || height < 25 {
+                    return Err(SolError::InvalidTermSize(width, height));
+                }
+                if let Err(e) = scr.resize(width, height) {
+                    return Err(SolError::Unexpected(format!("Failed to resize: {:?}", e)));
+                }
+                ctx.w = width;
+                ctx.h = height;
+            }
+        }
+        Ok(Transition::None)
+    }
+
+    fn draw(&self, ctx: &mut Context, scr: &mut Screen, theme: &dyn Theme) -> Result<(), SolError> {
+        let (fg, bg) = theme.base_colors();
+        scr.colors(fg, bg);
+        scr.clear();
+        draw_area(scr, &self.game, &ctx.state, theme)?;
+        self.draw_info(scr, theme);
+        Ok(())
+    }
+
+    fn on_activate(&mut self, _ctx: &mut Context) {}
+    fn on_deactivate(&self, _ctx: &mut Context) {}
+}
@@ -0,0 +1,96 @@
+use crossterm::event::{Event, KeyCode, MouseButton, MouseEvent};
+use crossterm::terminal;
+use unicode_width::UnicodeWidthStr;
+
+use solkit::err::SolError;
+
+use crate::primitive::{Border, Screen};
+use crate::strategy::{Context, Strategy, Transition, TransitionStage};
+use crate::theme::Theme;
+
+const ITEM_COUNT: usize = 2;
+const ITEM_HEIGHT: u16 = 3;
+const MENU_WIDTH: u16 = 28;
+const MENU_ITEMS: [&str; 2] = ["Resume saved game", "Start new game"];
+
+// menu displayed when a solitaire with an unfinished game is chosen
+pub(crate) struct ResumeStg {
+    selected: usize,
+}
+
+impl ResumeStg {
+    pub(crate) fn new(_ctx: &mut Context) -> Result<Self, SolError> {
+        Ok(ResumeStg { selected: 0 })
+    }
+}
+
+fn start_game(ctx: &mut Context, resume: bool) -> Transition {
+    ctx.resume = resume;
+    Transition::Replace(TransitionStage::Play)
+}
+
+impl Strategy for ResumeStg {
+    fn process_event(&mut self, ctx: &mut Context, scr: &mut Screen, event: Event) -> Result<Transition, SolError> {
+        match event {
+            Event::Key(ev) => match ev.code {
+                KeyCode::Esc => return Ok(Transition::Pop),
+                KeyCode::Up | KeyCode::Char('k') | KeyCode::Down | KeyCode::Char('j') => {
+                    self.selected = ITEM_COUNT - 1 - self.selected;
+                }
+                KeyCode::Enter => return Ok(start_game(ctx, self.selected == 0)),
+                _ => {}
+            },
+            Event::Mouse(ev) => {
+                if let MouseEvent::Down(MouseButton::Left, x, y, _) = ev {
+                    match scr.what_at(x, y) {
+                        1 => return Ok(start_game(ctx, true)),
+                        2 => return Ok(start_game(ctx, false)),
+                        _ => {}
+                    }
+                }
+            }
+            Event::Resize(_, _) => {
+                let (width, height) = match terminal::size() {
+                    Err(e) => return Err(SolError::Unexpected(format!("{:?}", e))),
+                    Ok((ww, hh)) => (ww, hh),
+                };
+                if width < 60 || height < 25 {
+                    return Err(SolError::InvalidTermSize(width, height));
+                }
+                if let Err(e) = scr.resize(width, height) {
+                    return Err(SolError::Unexpected(format!("Failed to resize: {:?}", e)));
+                }
+                ctx.w = width;
+                ctx.h = height;
+            }
+        }
+        Ok(Transition::None)
+    }
+
+    fn draw(&self, ctx: &mut Context, scr: &mut Screen, theme: &dyn Theme) -> Result<(), SolError> {
+        let x = ctx.w / 2 - MENU_WIDTH / 2;
+        let h = ITEM_COUNT as u16 * ITEM_HEIGHT + 2;
+        let y = ctx.h / 2 - h / 2;
+
+        let (fg, bg) = theme.base_colors();
+        scr.colors(fg, bg);
+        scr.draw_frame(x, y, MENU_WIDTH, h, Border::Double);
+        scr.write_string(&format!(" {} ", ctx.name), x + 1, y);
+        for (idx, item) in MENU_ITEMS.iter().enumerate() {
+            let (fg, bg) = if idx == self.selected { theme.menu_selected_item() } else { theme.base_colors() };
+            scr.kind(idx as u16 + 1);
+            scr.colors(fg, bg);
+            scr.fill_rect(x + 1, y + 1 + idx as u16 * ITEM_HEIGHT, MENU_WIDTH - 2, ITEM_HEIGHT, ' ');
+            let slen = item.width();
+            let mut shift = MENU_WIDTH / 2 - slen as u16 / 2;
+            if slen % 2 == 1 {
+                shift -= 1;
+            }
+            scr.write_string(MENU_ITEMS[idx], x + shift, y + 2 + idx as u16 * ITEM_HEIGHT);
+        }
+        Ok(())
+    }
+
+    fn on_activate(&mut self, _ctx: &mut Context) {}
+    fn on_deactivate(&self, _ctx: &mut Context) {}
+}
@@ -6,9 +6,12 @@ use std::{
 };
 
 use crate::loader;
-use solkit::card::{Card, Face, Suit};
+use solkit::card::{Ace, Card, DeckKind, Face, RankOrder, Suit};
 use solkit::err::SolError;
-use solkit::gconf::{ColConf, Conf, FaceOrder, FndSlot, PileConf, Playable, SuitOrder, TempConf};
+use solkit::gconf::{
+    places_from_rows, pyramid_places, ColConf, Collect, Conf, DealCols, FaceOrder, FndSlot, Goal, Layout, PileConf,
+    Playable, RedealMode, ReserveConf, SuitOrder, TempConf,
+};
 
 // return either pre-defined list of solitaires or a single one loaded from a file
 pub(crate) fn load_rules(filename: Option<String>) -> Result<HashMap<String, Conf>, SolError> {
@@ -62,8 +65,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Klondike (hard)".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder { wrap: false, ace: Ace::Low },
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 3, redeals: -1, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 3,
+            redeal_by: 0,
+            redeals: -1,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -74,15 +88,21 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             4
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 2, up: 1, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 4, up: 2, take_only: false },
-            ColConf { count: 5, up: 3, take_only: false },
-            ColConf { count: 6, up: 3, take_only: false },
-            ColConf { count: 7, up: 4, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(2, 1),
+            ColConf::new(3, 2),
+            ColConf::new(4, 2),
+            ColConf::new(5, 3),
+            ColConf::new(6, 3),
+            ColConf::new(7, 4),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -94,8 +114,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Klondike (easy)".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder { wrap: false, ace: Ace::Low },
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 1, redeals: -1, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: -1,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -106,15 +137,21 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             4
         ],
+        collect: Collect::Card,
+        fnd_playable: true,
+        fnd_penalty: 15,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 2, up: 1, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 4, up: 2, take_only: false },
-            ColConf { count: 5, up: 3, take_only: false },
-            ColConf { count: 6, up: 3, take_only: false },
-            ColConf { count: 7, up: 4, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(2, 1),
+            ColConf::new(3, 2),
+            ColConf::new(4, 2),
+            ColConf::new(5, 3),
+            ColConf::new(6, 3),
+            ColConf::new(7, 4),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -126,8 +163,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Klondike (double)".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder { wrap: false, ace: Ace::Low },
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 3, redeals: -1, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 3,
+            redeal_by: 0,
+            redeals: -1,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -138,16 +186,22 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             8
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 2, up: 1, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 4, up: 2, take_only: false },
-            ColConf { count: 5, up: 3, take_only: false },
-            ColConf { count: 6, up: 3, take_only: false },
-            ColConf { count: 7, up: 4, take_only: false },
-            ColConf { count: 8, up: 4, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(2, 1),
+            ColConf::new(3, 2),
+            ColConf::new(4, 2),
+            ColConf::new(5, 3),
+            ColConf::new(6, 3),
+            ColConf::new(7, 4),
+            ColConf::new(8, 4),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -159,7 +213,9 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Free cell".to_string(),
         chance: None,
         deck_count: 1,
-        playable: Playable::Top,
+        deck: DeckKind::full(),
+        rank_order: RankOrder { wrap: false, ace: Ace::Low },
+        playable: Playable::Supermove,
         pile: None,
         fnd: vec![
             FndSlot {
@@ -171,16 +227,22 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             4
         ],
-        temp: Some(TempConf { count: 4 }),
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
+        temp: Some(TempConf::new(4)),
+        reserve: None,
         cols: vec![
-            ColConf { count: 7, up: 7, take_only: false },
-            ColConf { count: 7, up: 7, take_only: false },
-            ColConf { count: 7, up: 7, take_only: false },
-            ColConf { count: 7, up: 7, take_only: false },
-            ColConf { count: 6, up: 6, take_only: false },
-            ColConf { count: 6, up: 6, take_only: false },
-            ColConf { count: 6, up: 6, take_only: false },
-            ColConf { count: 6, up: 6, take_only: false },
+            ColConf::new(7, 7),
+            ColConf::new(7, 7),
+            ColConf::new(7, 7),
+            ColConf::new(7, 7),
+            ColConf::new(6, 6),
+            ColConf::new(6, 6),
+            ColConf::new(6, 6),
+            ColConf::new(6, 6),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -192,6 +254,8 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Russian solitaire".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder { wrap: false, ace: Ace::Low },
         playable: Playable::Any,
         pile: None,
         fnd: vec![
@@ -204,15 +268,21 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             4
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 6, up: 5, take_only: false },
-            ColConf { count: 7, up: 5, take_only: false },
-            ColConf { count: 8, up: 5, take_only: false },
-            ColConf { count: 9, up: 5, take_only: false },
-            ColConf { count: 10, up: 5, take_only: false },
-            ColConf { count: 11, up: 5, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(6, 5),
+            ColConf::new(7, 5),
+            ColConf::new(8, 5),
+            ColConf::new(9, 5),
+            ColConf::new(10, 5),
+            ColConf::new(11, 5),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::SameSuit,
@@ -224,8 +294,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Pile\'em up".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Top,
-        pile: Some(PileConf { deal_by: 0, redeals: 0, pile_to_cols: true }),
+        pile: Some(PileConf {
+            deal_by: 0,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: true,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![FndSlot {
             first: Face::Any,
             suit: Suit::Any,
@@ -233,14 +314,20 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             sorder: SuitOrder::Any,
             filler: None,
         }],
-        temp: Some(TempConf { count: 2 }),
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
+        temp: Some(TempConf::new(2)),
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
         ],
         col_forder: FaceOrder::Any,
         col_sorder: SuitOrder::Forbid,
@@ -252,8 +339,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "American toad".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 1, redeals: 0, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -264,17 +362,23 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             8
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 20, up: 20, take_only: true },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
+            ColConf { take_only: true, ..ColConf::new(20, 20) },
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::SameSuit,
@@ -286,8 +390,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Auld lang syne".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Top,
-        pile: Some(PileConf { deal_by: 1, redeals: 0, pile_to_cols: true }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: true,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -318,13 +433,14 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
                 filler: Some(Card::new(Suit::Club, Face::A)),
             },
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
-        cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-        ],
+        reserve: None,
+        cols: vec![ColConf::new(1, 1), ColConf::new(1, 1), ColConf::new(1, 1), ColConf::new(1, 1)],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::Forbid,
         col_refill: Face::Any,
@@ -335,8 +451,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Aunt Mary".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 1, redeals: 0, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -347,14 +474,20 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             4
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 6, up: 6, take_only: false },
-            ColConf { count: 6, up: 5, take_only: false },
-            ColConf { count: 6, up: 4, take_only: false },
-            ColConf { count: 6, up: 3, take_only: false },
-            ColConf { count: 6, up: 2, take_only: false },
-            ColConf { count: 6, up: 1, take_only: false },
+            ColConf::new(6, 6),
+            ColConf::new(6, 5),
+            ColConf::new(6, 4),
+            ColConf::new(6, 3),
+            ColConf::new(6, 2),
+            ColConf::new(6, 1),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -366,8 +499,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Batsford".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder { wrap: false, ace: Ace::Low },
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 1, redeals: 0, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -378,18 +522,24 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             4
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 2, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 4, up: 1, take_only: false },
-            ColConf { count: 5, up: 1, take_only: false },
-            ColConf { count: 6, up: 1, take_only: false },
-            ColConf { count: 7, up: 1, take_only: false },
-            ColConf { count: 8, up: 1, take_only: false },
-            ColConf { count: 9, up: 1, take_only: false },
-            ColConf { count: 10, up: 1, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(2, 1),
+            ColConf::new(3, 1),
+            ColConf::new(4, 1),
+            ColConf::new(5, 1),
+            ColConf::new(6, 1),
+            ColConf::new(7, 1),
+            ColConf::new(8, 1),
+            ColConf::new(9, 1),
+            ColConf::new(10, 1),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -401,8 +551,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Blind alleys".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 1, redeals: 1, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 1,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -433,14 +594,20 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
                 filler: Some(Card::new(Suit::Club, Face::A)),
             },
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -452,6 +619,8 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Brigade".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Top,
         pile: None,
         fnd: vec![
@@ -484,16 +653,22 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
                 filler: Some(Card::new(Suit::Club, Face::A)),
             },
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 13, up: 13, take_only: true },
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf { take_only: true, ..ColConf::new(13, 13) },
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::Any,
@@ -505,8 +680,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Deuces".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 1, redeals: 0, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::N2,
@@ -517,18 +703,24 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             8
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::SameSuit,
@@ -540,8 +732,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Canfield".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 3, redeals: -1, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 3,
+            redeal_by: 0,
+            redeals: -1,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::Column,
@@ -552,14 +755,14 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             4
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
-        cols: vec![
-            ColConf { count: 14, up: 14, take_only: true },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-        ],
+        reserve: Some(ReserveConf { count: 13, up: 1, refill_cols: true }),
+        cols: vec![ColConf::new(1, 1), ColConf::new(1, 1), ColConf::new(1, 1), ColConf::new(1, 1)],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
         col_refill: Face::Any,
@@ -570,8 +773,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Double canfield".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 3, redeals: -1, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 3,
+            redeal_by: 0,
+            redeals: -1,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::Column,
@@ -582,15 +796,20 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             8
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: Some(ReserveConf { count: 40, up: 1, refill_cols: true }),
         cols: vec![
-            ColConf { count: 40, up: 40, take_only: true },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -602,8 +821,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Alhambra".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Top,
-        pile: Some(PileConf { deal_by: 1, redeals: 2, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 2,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -662,16 +892,22 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
                 filler: Some(Card::new(Suit::Club, Face::K)),
             },
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::Forbid,
@@ -683,8 +919,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Gargantua".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 1, redeals: 1, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 1,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -695,17 +942,23 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             8
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 2, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 4, up: 1, take_only: false },
-            ColConf { count: 5, up: 1, take_only: false },
-            ColConf { count: 6, up: 1, take_only: false },
-            ColConf { count: 7, up: 1, take_only: false },
-            ColConf { count: 8, up: 1, take_only: false },
-            ColConf { count: 9, up: 1, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(2, 1),
+            ColConf::new(3, 1),
+            ColConf::new(4, 1),
+            ColConf::new(5, 1),
+            ColConf::new(6, 1),
+            ColConf::new(7, 1),
+            ColConf::new(8, 1),
+            ColConf::new(9, 1),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -717,8 +970,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Lady Palk".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Top,
-        pile: Some(PileConf { deal_by: 1, redeals: 0, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -729,16 +993,22 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             8
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::Any,
@@ -750,8 +1020,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Red and black".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 1, redeals: 0, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -810,16 +1091,22 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
                 filler: Some(Card::new(Suit::Club, Face::A)),
             },
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 1, up: 1, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
+            ColConf::new(1, 1),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -831,8 +1118,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Steps".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 1, redeals: 1, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 1,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -843,15 +1141,21 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             8
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 2, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 4, up: 1, take_only: false },
-            ColConf { count: 5, up: 1, take_only: false },
-            ColConf { count: 6, up: 1, take_only: false },
-            ColConf { count: 7, up: 1, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(2, 1),
+            ColConf::new(3, 1),
+            ColConf::new(4, 1),
+            ColConf::new(5, 1),
+            ColConf::new(6, 1),
+            ColConf::new(7, 1),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -863,8 +1167,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Westcliff".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 1, redeals: 0, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -875,18 +1190,24 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             8
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
+            ColConf::new(3, 1),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -898,6 +1219,8 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Yukon".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder { wrap: false, ace: Ace::Low },
         playable: Playable::Any,
         pile: None,
         fnd: vec![
@@ -910,15 +1233,21 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             4
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 6, up: 5, take_only: false },
-            ColConf { count: 7, up: 5, take_only: false },
-            ColConf { count: 8, up: 5, take_only: false },
-            ColConf { count: 9, up: 5, take_only: false },
-            ColConf { count: 10, up: 5, take_only: false },
-            ColConf { count: 11, up: 5, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(6, 5),
+            ColConf::new(7, 5),
+            ColConf::new(8, 5),
+            ColConf::new(9, 5),
+            ColConf::new(10, 5),
+            ColConf::new(11, 5),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::AlternateColor,
@@ -930,6 +1259,8 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Good measure".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Top,
         pile: None,
         fnd: vec![
@@ -962,18 +1293,24 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
                 filler: None,
             },
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
-            ColConf { count: 5, up: 5, take_only: false },
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
+            ColConf::new(5, 5),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::Any,
@@ -985,8 +1322,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "But the same".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Top,
-        pile: Some(PileConf { deal_by: 1, redeals: 0, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -997,17 +1345,23 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             8
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
-            ColConf { count: 4, up: 4, take_only: false },
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
+            ColConf::new(4, 4),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::ExceptSame,
@@ -1019,8 +1373,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Indian".to_string(),
         chance: None,
         deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Top,
-        pile: Some(PileConf { deal_by: 1, redeals: 0, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -1031,18 +1396,24 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             8
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
-            ColConf { count: 3, up: 2, take_only: false },
+            ColConf::new(3, 2),
+            ColConf::new(3, 2),
+            ColConf::new(3, 2),
+            ColConf::new(3, 2),
+            ColConf::new(3, 2),
+            ColConf::new(3, 2),
+            ColConf::new(3, 2),
+            ColConf::new(3, 2),
+            ColConf::new(3, 2),
+            ColConf::new(3, 2),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::ExceptSame,
@@ -1054,8 +1425,19 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
         name: "Thumb and pouch".to_string(),
         chance: None,
         deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
         playable: Playable::Any,
-        pile: Some(PileConf { deal_by: 1, redeals: 0, pile_to_cols: false }),
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
         fnd: vec![
             FndSlot {
                 first: Face::A,
@@ -1066,15 +1448,21 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
             };
             4
         ],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
         temp: None,
+        reserve: None,
         cols: vec![
-            ColConf { count: 1, up: 1, take_only: false },
-            ColConf { count: 2, up: 1, take_only: false },
-            ColConf { count: 3, up: 1, take_only: false },
-            ColConf { count: 4, up: 1, take_only: false },
-            ColConf { count: 5, up: 1, take_only: false },
-            ColConf { count: 6, up: 1, take_only: false },
-            ColConf { count: 7, up: 1, take_only: false },
+            ColConf::new(1, 1),
+            ColConf::new(2, 1),
+            ColConf::new(3, 1),
+            ColConf::new(4, 1),
+            ColConf::new(5, 1),
+            ColConf::new(6, 1),
+            ColConf::new(7, 1),
         ],
         col_forder: FaceOrder::Desc,
         col_sorder: SuitOrder::ExceptSame,
@@ -1082,5 +1470,274 @@ fn builtin_rules() -> Result<HashMap<String, Conf>, SolError> {
     };
     rules.insert(conf.name.clone(), conf);
 
+    let conf = Conf {
+        name: "Spider".to_string(),
+        chance: None,
+        deck_count: 2,
+        deck: DeckKind::full(),
+        rank_order: RankOrder { wrap: false, ace: Ace::Low },
+        playable: Playable::SameSuit,
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: true,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: true,
+        }),
+        fnd: vec![
+            FndSlot {
+                first: Face::K,
+                suit: Suit::Any,
+                forder: FaceOrder::Desc,
+                sorder: SuitOrder::SameSuit,
+                filler: None,
+            };
+            8
+        ],
+        collect: Collect::Sequence,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
+        temp: None,
+        reserve: None,
+        cols: vec![
+            ColConf::new(6, 1),
+            ColConf::new(6, 1),
+            ColConf::new(6, 1),
+            ColConf::new(6, 1),
+            ColConf::new(5, 1),
+            ColConf::new(5, 1),
+            ColConf::new(5, 1),
+            ColConf::new(5, 1),
+            ColConf::new(5, 1),
+            ColConf::new(5, 1),
+        ],
+        col_forder: FaceOrder::Desc,
+        col_sorder: SuitOrder::Any,
+        col_refill: Face::Any,
+    };
+    // easier Spider variants use the same 104 cards made of fewer suits
+    let mut one_suit = conf.clone();
+    one_suit.name = "Spider (1 suit)".to_string();
+    one_suit.deck_count = 8;
+    one_suit.deck.set_suits(&[Suit::Spade]);
+    let mut two_suits = conf.clone();
+    two_suits.name = "Spider (2 suits)".to_string();
+    two_suits.deck_count = 4;
+    two_suits.deck.set_suits(&[Suit::Spade, Suit::Heart]);
+    rules.insert(conf.name.clone(), conf);
+    rules.insert(one_suit.name.clone(), one_suit);
+    rules.insert(two_suits.name.clone(), two_suits);
+
+    let conf = Conf {
+        name: "Scorpion".to_string(),
+        chance: None,
+        deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder { wrap: false, ace: Ace::Low },
+        playable: Playable::Any,
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: true,
+            deal_cols: DealCols::Subset(0b111),
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
+        fnd: vec![
+            FndSlot {
+                first: Face::K,
+                suit: Suit::Any,
+                forder: FaceOrder::Desc,
+                sorder: SuitOrder::SameSuit,
+                filler: None,
+            };
+            4
+        ],
+        collect: Collect::Sequence,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Columns,
+        temp: None,
+        reserve: None,
+        cols: vec![
+            ColConf::new(7, 4),
+            ColConf::new(7, 4),
+            ColConf::new(7, 4),
+            ColConf::new(7, 4),
+            ColConf::new(7, 7),
+            ColConf::new(7, 7),
+            ColConf::new(7, 7),
+        ],
+        col_forder: FaceOrder::Desc,
+        col_sorder: SuitOrder::SameSuit,
+        col_refill: Face::K,
+    };
+    rules.insert(conf.name.clone(), conf);
+
+    let conf = Conf {
+        name: "Pyramid".to_string(),
+        chance: None,
+        deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
+        playable: Playable::Top,
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 2,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
+        fnd: vec![FndSlot {
+            first: Face::Any,
+            suit: Suit::Any,
+            forder: FaceOrder::Any,
+            sorder: SuitOrder::Any,
+            filler: None,
+        }],
+        collect: Collect::Sum(13),
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::Tableau,
+        layout: Layout::Free(pyramid_places(7)),
+        temp: None,
+        reserve: None,
+        cols: vec![ColConf { take_only: true, ..ColConf::new(1, 1) }; 28],
+        col_forder: FaceOrder::Any,
+        col_sorder: SuitOrder::Any,
+        col_refill: Face::Unavail,
+    };
+    rules.insert(conf.name.clone(), conf);
+
+    let conf = Conf {
+        name: "Golf".to_string(),
+        chance: None,
+        deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder { wrap: false, ace: Ace::Low },
+        playable: Playable::Top,
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: true,
+            require_filled: false,
+        }),
+        fnd: vec![FndSlot {
+            first: Face::Any,
+            suit: Suit::Any,
+            forder: FaceOrder::Any,
+            sorder: SuitOrder::Any,
+            filler: None,
+        }],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::Tableau,
+        layout: Layout::Columns,
+        temp: None,
+        reserve: None,
+        cols: vec![ColConf { take_only: true, ..ColConf::new(5, 5) }; 7],
+        col_forder: FaceOrder::Any,
+        col_sorder: SuitOrder::Any,
+        col_refill: Face::Unavail,
+    };
+    rules.insert(conf.name.clone(), conf);
+
+    // three peaks of face-down cards over a row of 10 face-up cards
+    let peaks =
+        vec![vec![3, 9, 15], vec![2, 4, 8, 10, 14, 16], (1..18).step_by(2).collect(), (0..19).step_by(2).collect()];
+    let mut cols = vec![ColConf { take_only: true, ..ColConf::new(1, 0) }; 18];
+    cols.extend_from_slice(&[ColConf { take_only: true, ..ColConf::new(1, 1) }; 10]);
+    let conf = Conf {
+        name: "TriPeaks".to_string(),
+        chance: None,
+        deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
+        playable: Playable::Top,
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: true,
+            require_filled: false,
+        }),
+        fnd: vec![FndSlot {
+            first: Face::Any,
+            suit: Suit::Any,
+            forder: FaceOrder::Any,
+            sorder: SuitOrder::Any,
+            filler: None,
+        }],
+        collect: Collect::Card,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::Tableau,
+        layout: Layout::Free(places_from_rows(&peaks)),
+        temp: None,
+        reserve: None,
+        cols,
+        col_forder: FaceOrder::Any,
+        col_sorder: SuitOrder::Any,
+        col_refill: Face::Unavail,
+    };
+    rules.insert(conf.name.clone(), conf);
+
+    let conf = Conf {
+        name: "Monte Carlo".to_string(),
+        chance: None,
+        deck_count: 1,
+        deck: DeckKind::full(),
+        rank_order: RankOrder::default(),
+        playable: Playable::Top,
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: 0,
+            redeal: RedealMode::Turn,
+            pile_to_cols: true,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
+        fnd: vec![FndSlot {
+            first: Face::Any,
+            suit: Suit::Any,
+            forder: FaceOrder::Any,
+            sorder: SuitOrder::Any,
+            filler: None,
+        }],
+        collect: Collect::SameFace,
+        fnd_playable: false,
+        fnd_penalty: 0,
+        goal: Goal::AllCards,
+        layout: Layout::Grid(5),
+        temp: None,
+        reserve: None,
+        cols: vec![ColConf { take_only: true, ..ColConf::new(1, 1) }; 25],
+        col_forder: FaceOrder::Any,
+        col_sorder: SuitOrder::Any,
+        col_refill: Face::Unavail,
+    };
+    rules.insert(conf.name.clone(), conf);
+
     Ok(rules)
 }
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::fs::{read_to_string, write};
+use std::time::Duration;
+
+use serde_derive::{Deserialize, Serialize};
+
+use solkit::engine::SavedGame;
+
+use crate::config::saves_path;
+
+// unfinished game
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct SavedSession {
+    pub(crate) elapsed: Duration,
+    pub(crate) game: SavedGame,
+}
+
+// all unfinished games: one per solitaire
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Saves {
+    games: HashMap<String, SavedSession>,
+}
+
+impl Saves {
+    fn new() -> Self {
+        Saves { games: HashMap::new() }
+    }
+
+    pub(crate) fn load() -> Self {
+        let path = saves_path();
+        if !path.exists() {
+            return Saves::new();
+        }
+        let data = match read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Failed to load saved games: {:?}", e);
+                return Saves::new();
+            }
+        };
+        let saves: Saves = match toml::from_str(&data) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Failed to read TOML saved games: {:?}", e);
+                Saves::new()
+            }
+        };
+        saves
+    }
+
+    pub(crate) fn has(&self, name: &str) -> bool {
+        self.games.contains_key(name)
+    }
+
+    pub(crate) fn put(&mut self, name: &str, session: SavedSession) {
+        self.games.insert(name.to_string(), session);
+    }
+
+    // remove the saved game from the list and return it
+    pub(crate) fn take(&mut self, name: &str) -> Option<SavedSession> {
+        self.games.remove(name)
+    }
+
+    pub(crate) fn save(&self) {
+        let tml = match toml::to_string(&self) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Failed to serialize saved games: {:?}", e);
+                return;
+            }
+        };
+        let path = saves_path();
+        if let Err(e) = write(path, tml) {
+            eprintln!("Failed to save games: {:?}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod saves_test {
+    use super::*;
+    use crate::rules::load_rules;
+    use solkit::engine::{Game, Pos};
+
+    #[test]
+    fn toml_roundtrip() {
+        let rules = load_rules(None).unwrap();
+        let conf = &rules["Klondike (easy)"];
+        let mut game = Game::init(conf.clone(), Some(42)).unwrap();
+        game.take_snapshot();
+        game.deal();
+        game.take_snapshot();
+        game.deal();
+        game.undo();
+        let mut saves = Saves::new();
+        saves.put(&conf.name, SavedSession { elapsed: Duration::new(75, 0), game: game.save() });
+
+        let tml = toml::to_string(&saves).unwrap();
+        let mut loaded: Saves = toml::from_str(&tml).unwrap();
+        assert!(loaded.has(&conf.name));
+        let session = loaded.take(&conf.name).unwrap();
+        assert_eq!(session.elapsed, Duration::new(75, 0));
+        let restored = Game::restore(conf.clone(), &session.game).unwrap();
+        assert_eq!(restored.deal_number(), 42);
+        assert_eq!(restored.undo_count(), game.undo_count());
+        assert!(restored.has_redo());
+        assert_eq!(restored.log(), game.log());
+        for col in 0..restored.column_count() {
+            let pos = Pos { col, row: 0 };
+            assert_eq!(restored.slot_cards_left(col).unwrap(), game.slot_cards_left(col).unwrap());
+            assert_eq!(restored.card_at(pos).face, game.card_at(pos).face);
+            assert_eq!(restored.card_at(pos).suit, game.card_at(pos).suit);
+        }
+    }
+
+    #[test]
+    fn toml_empty_history() {
+        let rules = load_rules(None).unwrap();
+        let conf = &rules["Klondike (easy)"];
+        let mut game = Game::init(conf.clone(), Some(42)).unwrap();
+        let roundtrip = |game: &Game| {
+            let mut saves = Saves::new();
+            saves.put(&conf.name, SavedSession { elapsed: Duration::new(5, 0), game: game.save() });
+            let tml = toml::to_string(&saves).unwrap();
+            let mut loaded: Saves = toml::from_str(&tml).unwrap();
+            Game::restore(conf.clone(), &loaded.take(&conf.name).unwrap().game).unwrap()
+        };
+        // a fresh game has neither undo nor redo entries
+        let restored = roundtrip(&game);
+        assert!(restored.undo_count() == 0 && !restored.has_redo());
+        game.take_snapshot();
+        game.deal();
+        let restored = roundtrip(&game);
+        assert!(restored.undo_count() == 1 && !restored.has_redo());
+    }
+}
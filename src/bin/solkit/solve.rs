@@ -0,0 +1,106 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+
+use solkit::card::{card_to_str, random_deal};
+use solkit::engine::{Game, Pos};
+use solkit::gconf::Conf;
+use solkit::solver::{self, Limits, Move, Outcome};
+
+use crate::opts::CliOpts;
+use crate::rules;
+
+// find a solitaire by its name(case-insensitive) or load it from a file
+pub(crate) fn find_conf(target: &str) -> Result<Conf> {
+    if Path::new(target).is_file() {
+        let rules = rules::load_rules(Some(target.to_string()))?;
+        if let Some((_, conf)) = rules.into_iter().next() {
+            return Ok(conf);
+        }
+    } else {
+        let low = target.to_lowercase();
+        for (name, conf) in rules::load_rules(None)?.into_iter() {
+            if name.to_lowercase() == low {
+                return Ok(conf);
+            }
+        }
+    }
+    Err(anyhow!("Solitaire '{}' not found", target))
+}
+
+// human-readable name of a pile
+fn pile_name(game: &Game, col: usize) -> String {
+    let groups = [
+        (game.first_fnd(), game.fnd_count(), "foundation"),
+        (game.first_col(), game.col_count(), "column"),
+        (game.first_temp(), game.temp_count(), "free cell"),
+    ];
+    for (first, count, name) in groups.iter() {
+        if let Some(first) = first {
+            if col >= *first && col < first + count {
+                return format!("{} {}", name, col - first + 1);
+            }
+        }
+    }
+    if game.first_reserve() == Some(col) {
+        return "reserve".to_string();
+    }
+    match game.first_pile() {
+        Some(first) if col == first => "deck".to_string(),
+        _ => "waste".to_string(),
+    }
+}
+
+// human-readable description of a move that is about to be made
+pub(crate) fn describe_move(game: &Game, mv: Move) -> String {
+    match mv {
+        Move::Deal => "Deal".to_string(),
+        Move::Redeal => "Redeal".to_string(),
+        Move::Pair { a, b } => {
+            let (ca, cb) = (game.card_at(Pos { col: a, row: 0 }), game.card_at(Pos { col: b, row: 0 }));
+            format!("{} + {}: {} + {}", card_to_str(&ca), card_to_str(&cb), pile_name(game, a), pile_name(game, b))
+        }
+        Move::Card { from, to, count } => {
+            let card = game.card_at(Pos { col: from, row: count.saturating_sub(1) });
+            let cnt = if count > 1 { format!(" (+{} cards)", count - 1) } else { String::new() };
+            format!("{}{}: {} -> {}", card_to_str(&card), cnt, pile_name(game, from), pile_name(game, to))
+        }
+    }
+}
+
+fn print_moves(conf: &Conf, seed: u64, moves: &[Move]) -> Result<()> {
+    let mut game = Game::init(conf.clone(), Some(seed))?;
+    for (idx, mv) in moves.iter().enumerate() {
+        println!("{:4}. {}", idx + 1, describe_move(&game, *mv));
+        game.apply(*mv)?;
+    }
+    Ok(())
+}
+
+// solve a deal and print the solution to stdout
+pub(crate) fn run(cli: &CliOpts, target: &str) -> Result<()> {
+    let conf = find_conf(target)?;
+    let seed = cli.deal.unwrap_or_else(random_deal);
+    let mut limits = Limits::default();
+    if let Some(nodes) = cli.nodes {
+        limits.nodes = nodes;
+    }
+    if let Some(tm) = cli.time {
+        limits.time = if tm == 0 { None } else { Some(Duration::from_secs(tm)) };
+    }
+
+    println!("Solitaire: {}", conf.name);
+    println!("Deal: {}", seed);
+    let solution = solver::solve(&conf, seed, &limits)?;
+    let stats = format!("{} positions checked, {:.2}s", solution.nodes, solution.elapsed.as_secs_f64());
+    match solution.outcome {
+        Outcome::Solved(ref moves) => {
+            println!("Solved in {} moves ({})", moves.len(), stats);
+            print_moves(&conf, seed, moves)?;
+        }
+        Outcome::Unsolvable => println!("The deal cannot be won ({})", stats),
+        Outcome::GaveUp => println!("No solution found within the limits ({})", stats),
+    }
+    Ok(())
+}
@@ -19,19 +19,19 @@ pub(crate) fn duration_to_human(dur: Option<Duration>) -> String {
         return String::new();
     }
     if d >= 98 * DAY_SEC {
-        return format!("{}d", (d - 1 + DAY_SEC / 2) / DAY_SEC);
+        format!("{}d", (d - 1 + DAY_SEC / 2) / DAY_SEC)
     } else if d >= DAY_SEC {
         let days = d / DAY_SEC;
         let hours = (d - days * DAY_SEC + HOUR_SEC / 2 - 1) / HOUR_SEC;
-        return format!("{}d{}h", days, hours);
+        format!("{}d{}h", days, hours)
     } else if d >= HOUR_SEC {
         let hours = d / HOUR_SEC;
         let mins = (d - hours * HOUR_SEC + MIN_SEC / 2 - 1) / MIN_SEC;
-        return format!("{}h{}m", hours, mins);
+        format!("{}h{}m", hours, mins)
     } else {
         let mins = d / MIN_SEC;
         let secs = d - mins * MIN_SEC;
-        return format!("{}m{}s", mins, secs);
+        format!("{}m{}s", mins, secs)
     }
 }
 
@@ -40,12 +40,14 @@ pub(crate) fn duration_to_human(dur: Option<Duration>) -> String {
 pub(crate) struct GameStat {
     pub(crate) played: u64,
     pub(crate) won: u64,
+    #[serde(default)]
+    pub(crate) lost: u64, // games finished with no moves left. The rest of not won games are abandoned ones
     pub(crate) spent: Option<Duration>,
 }
 
 impl Default for GameStat {
     fn default() -> GameStat {
-        GameStat { played: 0, won: 0, spent: Some(Duration::new(0, 0)) }
+        GameStat { played: 0, won: 0, lost: 0, spent: Some(Duration::new(0, 0)) }
     }
 }
 
@@ -82,8 +84,8 @@ impl Stats {
         stats
     }
 
-    pub(crate) fn update_stat(&mut self, name: &str, won: bool, spent: Duration) {
-        let stat = self.games.entry(name.to_string()).or_insert_with(Default::default);
+    pub(crate) fn update_stat(&mut self, name: &str, won: bool, lost: bool, spent: Duration) {
+        let stat = self.games.entry(name.to_string()).or_default();
         stat.played += 1;
         let old = match stat.spent {
             None => Duration::new(0, 0),
@@ -92,6 +94,8 @@ impl Stats {
         stat.spent = Some(old + spent);
         if won {
             stat.won += 1;
+        } else if lost {
+            stat.lost += 1;
         }
     }
 
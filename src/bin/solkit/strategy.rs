@@ -2,10 +2,12 @@ use std::time::{Duration, SystemTime};
 
 use crossterm::event::Event;
 
+use solkit::engine::SavedGame;
 use solkit::err::SolError;
 
 use crate::gstate::GameState;
 use crate::primitive::Screen;
+use crate::saves::Saves;
 use crate::stats::Stats;
 use crate::theme::Theme;
 
@@ -14,6 +16,8 @@ pub(crate) enum TransitionStage {
     Choose,
     EndDialog,
     HelpDialog,
+    ResumeDialog,
+    LostDialog,
 }
 
 pub(crate) enum Transition {
@@ -30,9 +34,17 @@ pub(crate) struct Context {
     pub(crate) w: u16, // screen width
     pub(crate) h: u16, // screen height
     pub(crate) stats: Stats,
-    pub(crate) moved: bool, // to avoid changing stats if no move was done
+    pub(crate) saves: Saves,
+    pub(crate) saved: Option<SavedGame>, // the latest state of the game in progress
+    pub(crate) resume: bool,             // continue the saved game instead of starting a new one
+    pub(crate) moved: bool,              // to avoid changing stats if no move was done
     pub(crate) won: bool,
-    pub(crate) custom: bool, // app launched with a custom solitaire
+    pub(crate) lost: bool,        // no moves left
+    pub(crate) undo: bool,        // undo the last move when returning to the game
+    pub(crate) custom: bool,      // app launched with a custom solitaire
+    pub(crate) deal: Option<u64>, // deal number for the next game (random if None)
+    pub(crate) autoplay: bool,    // move safe cards to foundations after every move
+    pub(crate) undo_limit: usize, // maximum number of moves to undo (0 - unlimited)
     pub(crate) elapsed: Duration,
     started: SystemTime,
 }
@@ -40,7 +52,7 @@ pub(crate) struct Context {
 pub(crate) trait Strategy {
     fn process_event(&mut self, ctx: &mut Context, scr: &mut Screen, event: Event) -> Result<Transition, SolError>;
     fn draw(&self, ctx: &mut Context, scr: &mut Screen, theme: &dyn Theme) -> Result<(), SolError>;
-    fn on_activate(&self, ctx: &mut Context);
+    fn on_activate(&mut self, ctx: &mut Context);
     fn on_deactivate(&self, ctx: &mut Context);
 }
 
@@ -52,9 +64,17 @@ impl Context {
             w: cols,
             h: rows,
             stats: Stats::load(),
+            saves: Saves::load(),
+            saved: None,
+            resume: false,
             moved: false,
             won: false,
+            lost: false,
+            undo: false,
             custom: false,
+            deal: None,
+            autoplay: false,
+            undo_limit: 0,
             elapsed: Duration::new(0, 0),
             started: SystemTime::now(),
         }
@@ -67,6 +87,13 @@ impl Context {
     pub(crate) fn unpause(&mut self) {
         self.started = SystemTime::now();
     }
+    // game time including the time since the last unpause
+    pub(crate) fn play_time(&self) -> Duration {
+        match self.started.elapsed() {
+            Ok(elapsed) => self.elapsed + elapsed,
+            Err(_) => self.elapsed,
+        }
+    }
     pub(crate) fn reset(&mut self) {
         self.started = SystemTime::now();
         self.elapsed = Duration::new(0, 0);
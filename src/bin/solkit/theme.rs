@@ -12,6 +12,7 @@ pub(crate) trait Theme {
     fn hint_card(&self) -> (Color, Color);
     fn selected_card(&self) -> (Color, Color);
     fn suit(&self, s: Suit) -> Color;
+    fn joker(&self) -> Color;
 
     fn hint_letter(&self) -> (Color, Color);
     fn win_msg(&self) -> (Color, Color);
@@ -82,6 +83,9 @@ impl Theme for DarkTheme {
             }
         }
     }
+    fn joker(&self) -> Color {
+        Color::Yellow
+    }
 
     fn hint_letter(&self) -> (Color, Color) {
         self.base_colors()
@@ -138,6 +142,9 @@ impl Theme for LightTheme {
             }
         }
     }
+    fn joker(&self) -> Color {
+        Color::DarkYellow
+    }
 
     fn hint_letter(&self) -> (Color, Color) {
         self.base_colors()
@@ -3,6 +3,7 @@ use std::cmp;
 use solkit::card::{Card, Face, Suit};
 use solkit::engine::{Game, Pos, SlotConf};
 use solkit::err::SolError;
+use solkit::gconf::Layout;
 
 use crate::gstate::GameState;
 use crate::primitive::{Border, Screen};
@@ -55,6 +56,7 @@ pub(crate) fn face_to_str(f: Face) -> &'static str {
         Face::Q => "Q",
         Face::K => "K",
         Face::A => "A",
+        Face::Joker => "JK",
         Face::Any => "?",
         _ => "",
     }
@@ -108,7 +110,7 @@ fn draw_card_down(scr: &mut Screen, col: u16, row: u16, border: Border, theme: &
     scr.colors(fg, bg);
     for y in row + 1..row + 1 + CARD_HEIGHT - 2 {
         for x in col + 1..col + 1 + CARD_WIDTH - 2 {
-            let c = if (x - col - 1) % 2 == 0 { '▀' } else { '▄' };
+            let c = if (x - col - 1).is_multiple_of(2) { '▀' } else { '▄' };
             scr.write_char(c, x, y)
         }
     }
@@ -198,6 +200,10 @@ pub(crate) fn draw_card(
     scr.fill_rect(col + 1, row + 1, CARD_WIDTH - 2, CARD_HEIGHT - 2, ' ');
 
     let fy = if flags.contains(CardState::SQUASH) { row } else { row + 1 };
+    if card.is_joker() {
+        draw_joker(scr, col, row, fy, card, theme);
+        return;
+    }
     let fstr = face_to_str(card.face);
     scr.write_string(fstr, col + 1, fy);
     let last_row = row + CARD_HEIGHT - 2;
@@ -210,6 +216,21 @@ pub(crate) fn draw_card(
     scr.colors(fg, bg);
 }
 
+// a joker has no suit: its corners show "JK" in the joker color(black or red) and a star.
+// The card frame must be drawn before calling the function
+fn draw_joker(scr: &mut Screen, col: u16, row: u16, fy: u16, card: Card, theme: &dyn Theme) {
+    let (_, bg) = theme.card();
+    let last_row = row + CARD_HEIGHT - 2;
+    scr.colors(theme.suit(card.suit), bg);
+    scr.write_string("JK", col + 1, fy);
+    scr.write_string("JK", col + 3, last_row);
+    scr.colors(theme.joker(), bg);
+    scr.write_char('★', col + 4, fy);
+    scr.write_char('★', col + 1, last_row);
+    let (fg, bg) = theme.base_colors();
+    scr.colors(fg, bg);
+}
+
 fn is_in_list(val: Pos, list: &[Pos]) -> bool {
     for lv in list {
         if *lv == val {
@@ -350,11 +371,68 @@ pub(crate) fn draw_pile(
     }
 }
 
+// position of a column relative to the top left corner of the column area. Cards of a grid and
+// a free layout overlap, so only the top lines of the covered cards are visible
+fn col_shift(game: &Game, cidx: usize) -> ScrPos {
+    match game.layout() {
+        Layout::Columns => ScrPos { col: cidx as u16 * (CARD_WIDTH + 1), row: 0 },
+        Layout::Grid(w) => {
+            let w = usize::from(*w);
+            ScrPos { col: (cidx % w) as u16 * (CARD_WIDTH + 1), row: (cidx / w) as u16 * (CARD_HEIGHT - 2) }
+        }
+        Layout::Free(places) => {
+            let place = &places[cidx];
+            ScrPos { col: u16::from(place.x) * (CARD_WIDTH + 1) / 2, row: u16::from(place.y) * 2 }
+        }
+    }
+}
+
+// width of the column area
+fn cols_width(game: &Game) -> u16 {
+    let col_cnt = game.col_count() as u16;
+    match game.layout() {
+        Layout::Columns => col_cnt * CARD_WIDTH + col_cnt,
+        Layout::Grid(w) => u16::from(*w) * (CARD_WIDTH + 1),
+        Layout::Free(_) => {
+            (0..game.col_count()).map(|idx| col_shift(game, idx).col + CARD_WIDTH + 1).max().unwrap_or(0)
+        }
+    }
+}
+
+// the order of drawing columns: a column goes after all columns it covers, so the topmost card
+// is drawn last, and it is the one that the mouse clicks
+fn draw_order(game: &Game) -> Vec<usize> {
+    let cnt = game.col_count();
+    let places = match game.layout() {
+        Layout::Free(places) => places,
+        _ => return (0..cnt).collect(),
+    };
+    // the length of the longest chain of columns under a column. The number of passes is limited
+    // to stop on columns covering each other
+    let mut depth = vec![0usize; cnt];
+    for _ in 0..cnt {
+        let mut changed = false;
+        for (idx, place) in places.iter().enumerate() {
+            for &c in place.covered_by.iter() {
+                if depth[c] <= depth[idx] {
+                    depth[c] = depth[idx] + 1;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    let mut order: Vec<usize> = (0..cnt).collect();
+    order.sort_by_key(|&idx| depth[idx]);
+    order
+}
+
 pub(crate) fn area_width(game: &Game) -> u16 {
     let pile_cnt = game.pile_count();
     let fnd_cnt = game.fnd_count();
-    let temp_cnt = game.temp_count();
-    let col_cnt = game.col_count();
+    let side_cnt = game.temp_count() + game.reserve_count();
 
     let top_shift = if pile_cnt == 0 {
         1
@@ -363,10 +441,10 @@ pub(crate) fn area_width(game: &Game) -> u16 {
     } else {
         CARD_WIDTH + CARD_WIDTH / 2 + 1
     };
-    let bottom_shift = if temp_cnt == 0 { 1 } else { CARD_WIDTH + CARD_WIDTH / 2 + 1 };
+    let bottom_shift = if side_cnt == 0 { 1 } else { CARD_WIDTH + CARD_WIDTH / 2 + 1 };
 
     let fnd_w = fnd_cnt as u16 * CARD_WIDTH + fnd_cnt as u16;
-    let cols_w = col_cnt as u16 * CARD_WIDTH + col_cnt as u16;
+    let cols_w = cols_width(game);
 
     let top = fnd_w + top_shift;
     let bottom = cols_w + bottom_shift;
@@ -379,7 +457,6 @@ pub(crate) fn draw_area(scr: &mut Screen, game: &Game, state: &GameState, theme:
     let pile_cnt = game.pile_count();
     let temp_cnt = game.temp_count();
     let fnd_cnt = game.fnd_count();
-    let col_cnt = game.col_count();
     let marked = state.marked();
     let hints = state.hints();
 
@@ -409,7 +486,7 @@ pub(crate) fn draw_area(scr: &mut Screen, game: &Game, state: &GameState, theme:
                 ScrPos { col: idx + CARD_WIDTH + 1, row: idy },
                 10, // pile height is always one card high
                 PileProps { pile: lst, id: id + 1, conf: cfg },
-                DrawHints { selected: marked, current: game.selected_loc(), hinted: &hints },
+                DrawHints { selected: marked, current: game.selected_loc(), hinted: hints },
                 theme,
             );
         }
@@ -431,7 +508,7 @@ pub(crate) fn draw_area(scr: &mut Screen, game: &Game, state: &GameState, theme:
             ScrPos { col: idx, row: y },
             10, // temp height is always one card high
             PileProps { pile: lst, id: fid + tidx, conf: cfg },
-            DrawHints { selected: marked, current: game.selected_loc(), hinted: &hints },
+            DrawHints { selected: marked, current: game.selected_loc(), hinted: hints },
             theme,
         );
         if tidx == 0 {
@@ -442,7 +519,30 @@ pub(crate) fn draw_area(scr: &mut Screen, game: &Game, state: &GameState, theme:
         }
     }
 
-    let xshift = if pile_cnt == 0 && temp_cnt == 0 {
+    if let Some(rid) = game.first_reserve() {
+        let (fg, bg) = theme.base_colors();
+        scr.colors(fg, bg);
+        // the reserve goes right after free cells
+        let y = idy + CARD_HEIGHT + 2 + temp_cnt as u16 * CARD_HEIGHT;
+        let cfg = game.slot_conf(rid)?;
+        let lst = game.reserve()?;
+        draw_pile(
+            scr,
+            ScrPos { col: idx, row: y },
+            10, // reserve height is always one card high
+            PileProps { pile: lst, id: rid, conf: cfg },
+            DrawHints { selected: marked, current: game.selected_loc(), hinted: hints },
+            theme,
+        );
+        let (fg, bg) = theme.hint_letter();
+        scr.colors(fg, bg);
+        scr.kind(0);
+        scr.write_char('e', idx - 1, y);
+    }
+
+    // free cells and reserve share the left side of the play area
+    let side_cnt = temp_cnt + game.reserve_count();
+    let xshift = if pile_cnt == 0 && side_cnt == 0 {
         0
     } else if pile_cnt == 2 {
         2 * CARD_WIDTH + 1 + CARD_WIDTH / 2
@@ -461,7 +561,7 @@ pub(crate) fn draw_area(scr: &mut Screen, game: &Game, state: &GameState, theme:
             ScrPos { col: x, row: idy },
             10, // fnd height is always is one card high
             PileProps { pile: lst, id: fid + widx, conf: cfg },
-            DrawHints { selected: marked, current: game.selected_loc(), hinted: &hints },
+            DrawHints { selected: marked, current: game.selected_loc(), hinted: hints },
             theme,
         );
         if widx == 0 {
@@ -473,22 +573,40 @@ pub(crate) fn draw_area(scr: &mut Screen, game: &Game, state: &GameState, theme:
         }
     }
 
-    let xshift = if temp_cnt == 0 { 0 } else { CARD_WIDTH + CARD_WIDTH / 2 };
-    for cidx in 0..col_cnt {
+    let xshift = if side_cnt == 0 { 0 } else { CARD_WIDTH + CARD_WIDTH / 2 };
+    for cidx in draw_order(game) {
         let (fg, bg) = theme.base_colors();
         scr.colors(fg, bg);
         let fid = game.first_col().unwrap();
-        let y = idy + 2 + CARD_HEIGHT;
-        let x = idx + xshift + cidx as u16 * CARD_WIDTH + cidx as u16;
+        let shift = col_shift(game, cidx);
+        let y = idy + 2 + CARD_HEIGHT + shift.row;
+        let x = idx + xshift + shift.col;
         let cfg = game.slot_conf(fid + cidx)?;
         let lst = game.col(cidx)?;
+        if *game.layout() != Layout::Columns {
+            // removed cards leave no trace, and there is no room for column numbers
+            if !lst.is_empty() {
+                // hide the bottom lines of covered cards in the gaps around the card
+                scr.kind(0);
+                scr.fill_rect(x - 1, y, CARD_WIDTH + 2, CARD_HEIGHT, ' ');
+                draw_pile(
+                    scr,
+                    ScrPos { col: x, row: y },
+                    CARD_HEIGHT,
+                    PileProps { pile: lst, id: fid + cidx, conf: cfg },
+                    DrawHints { selected: marked, current: game.selected_loc(), hinted: hints },
+                    theme,
+                );
+            }
+            continue;
+        }
         let max_height = scr.height() - y;
         draw_pile(
             scr,
             ScrPos { col: x, row: y },
             max_height,
             PileProps { pile: lst, id: fid + cidx, conf: cfg },
-            DrawHints { selected: marked, current: game.selected_loc(), hinted: &hints },
+            DrawHints { selected: marked, current: game.selected_loc(), hinted: hints },
             theme,
         );
 
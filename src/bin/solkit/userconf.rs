@@ -4,15 +4,13 @@ use serde_derive::{Deserialize, Serialize};
 
 use crate::config::user_conf_path;
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub(crate) struct UserConf {
     pub(crate) last_played: String,
-}
-
-impl Default for UserConf {
-    fn default() -> UserConf {
-        UserConf { last_played: String::new() }
-    }
+    #[serde(default)]
+    pub(crate) autoplay: bool, // move safe cards to foundations after every move
+    #[serde(default)]
+    pub(crate) undo_limit: usize, // maximum number of moves to undo (0 - unlimited)
 }
 
 impl UserConf {
@@ -1,8 +1,9 @@
 use nanorand::{WyRand, RNG};
+use serde_derive::{Deserialize, Serialize};
 
 use crate::err::SolError;
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub enum Suit {
     Spade,
     Club,
@@ -10,7 +11,7 @@ pub enum Suit {
     Heart,
     Any,
 }
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub enum Face {
     N2,
     N3,
@@ -25,6 +26,7 @@ pub enum Face {
     Q,
     K,
     A,
+    Joker,
     Empty,
     Any,
     Unavail,
@@ -92,6 +94,7 @@ pub fn str_to_face(f: &str) -> Result<Face, SolError> {
         "q" => Ok(Face::Q),
         "k" => Ok(Face::K),
         "a" => Ok(Face::A),
+        "jk" | "joker" => Ok(Face::Joker),
         "any" => Ok(Face::Any),
         "empty" => Ok(Face::Empty),
         "first" | "column" | "random" => Ok(Face::Column),
@@ -118,6 +121,92 @@ fn face_to_i8(f: Face) -> i8 {
     }
 }
 
+// short card names used in saved games: a face followed by the first letter of a suit
+pub fn face_to_code(f: Face) -> &'static str {
+    match f {
+        Face::A => "A",
+        Face::N2 => "2",
+        Face::N3 => "3",
+        Face::N4 => "4",
+        Face::N5 => "5",
+        Face::N6 => "6",
+        Face::N7 => "7",
+        Face::N8 => "8",
+        Face::N9 => "9",
+        Face::N10 => "10",
+        Face::J => "J",
+        Face::Q => "Q",
+        Face::K => "K",
+        Face::Joker => "JK",
+        _ => panic!("Invalid face"),
+    }
+}
+fn suit_to_code(s: Suit) -> char {
+    match s {
+        Suit::Spade => 's',
+        Suit::Club => 'c',
+        Suit::Diamond => 'd',
+        Suit::Heart => 'h',
+        Suit::Any => panic!("Invalid suit"),
+    }
+}
+
+// convert a card to a short text, e.g. "10h". A face-down card starts with '*'
+pub fn card_to_str(card: &Card) -> String {
+    let prefix = if card.up { "" } else { "*" };
+    format!("{}{}{}", prefix, face_to_code(card.face), suit_to_code(card.suit))
+}
+pub fn str_to_card(s: &str) -> Result<Card, SolError> {
+    let (up, code) = if let Some(stripped) = s.strip_prefix('*') { (false, stripped) } else { (true, s) };
+    let suit = match code.chars().last() {
+        Some('s') => Suit::Spade,
+        Some('c') => Suit::Club,
+        Some('d') => Suit::Diamond,
+        Some('h') => Suit::Heart,
+        _ => return Err(SolError::InvalidCard(s.to_string())),
+    };
+    let face = match str_to_face(&code[..code.len() - 1].to_lowercase()) {
+        Ok(f) => f,
+        Err(_) => return Err(SolError::InvalidCard(s.to_string())),
+    };
+    let card = Card { up, suit, face };
+    if !card.is_regular() {
+        return Err(SolError::InvalidCard(s.to_string()));
+    }
+    Ok(card)
+}
+// convert a list of cards to a space-separated string of short card names
+pub fn cards_to_str(cards: &[Card]) -> String {
+    let v: Vec<String> = cards.iter().map(card_to_str).collect();
+    v.join(" ")
+}
+pub fn str_to_cards(s: &str) -> Result<Vec<Card>, SolError> {
+    let mut cards = Vec::new();
+    for code in s.split_whitespace() {
+        cards.push(str_to_card(code)?);
+    }
+    Ok(cards)
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Ace {
+    Low,  // A goes before 2
+    High, // A goes after K
+}
+
+// how card ranks follow each other in sequences
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RankOrder {
+    pub wrap: bool, // the lowest and the highest ranks are neighbors: A can go on K and K can go on A
+    pub ace: Ace,
+}
+
+impl Default for RankOrder {
+    fn default() -> Self {
+        RankOrder { wrap: true, ace: Ace::Low }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct Card {
     pub face: Face,
@@ -140,15 +229,34 @@ impl Card {
         let that_red = other.suit == Suit::Diamond || other.suit == Suit::Heart;
         (this_red && that_red) || (!this_red && !that_red)
     }
-    // positive difference means other's face value is higher
-    pub fn diff(&self, other: &Card) -> i8 {
-        if other.face == Face::A && self.face == Face::K {
-            return 1;
-        }
-        if self.face == Face::A && other.face == Face::K {
-            return -1;
+    // positive difference means other's face value is higher. Ranks follow the given order: with
+    // `wrap` K and A are neighbors, and a high ace goes after K
+    pub fn diff(&self, other: &Card, order: RankOrder) -> i8 {
+        let rank = |f: Face| {
+            let r = face_to_i8(f) as usize;
+            match order.ace {
+                Ace::Low => r,
+                Ace::High if f == Face::A => 12,
+                Ace::High => r - 1,
+            }
+        };
+        rank_diff(rank(self.face), rank(other.face), 13, order.wrap)
+    }
+    // a joker is a wild card: it can take the place of any card
+    pub fn is_joker(&self) -> bool {
+        self.face == Face::Joker
+    }
+    // the same as `diff` but counts only the given ranks: in a stripped deck the rank that
+    // follows 10 may be A, or 6 may be the next one after A. The list must be sorted from A to K
+    pub fn diff_in(&self, other: &Card, faces: &[Face], order: RankOrder) -> i8 {
+        match (rank_pos(self.face, faces, order.ace), rank_pos(other.face, faces, order.ace)) {
+            (Some(mine), Some(theirs)) => rank_diff(mine, theirs, faces.len(), order.wrap),
+            _ => self.diff(other, order),
         }
-        face_to_i8(other.face) - face_to_i8(self.face)
+    }
+    // numeric value of a card face: A is 1, J is 11, Q is 12, and K is 13
+    pub fn value(&self) -> u8 {
+        (face_to_i8(self.face) + 1) as u8
     }
     pub fn is_empty(&self) -> bool {
         self.face == Face::Empty
@@ -165,31 +273,160 @@ impl Card {
     }
 }
 
+// the position of a face among the ranks of a deck sorted from A to K: a high ace goes after K
+fn rank_pos(face: Face, faces: &[Face], ace: Ace) -> Option<usize> {
+    let pos = faces.iter().position(|f| *f == face)?;
+    if ace == Ace::High && faces[0] == Face::A {
+        return Some(if pos == 0 { faces.len() - 1 } else { pos - 1 });
+    }
+    Some(pos)
+}
+
+// the difference between two rank positions. With `wrap` the lowest rank follows the highest one
+fn rank_diff(mine: usize, theirs: usize, count: usize, wrap: bool) -> i8 {
+    let last = count - 1;
+    if wrap && mine == last && theirs == 0 {
+        return 1;
+    }
+    if wrap && mine == 0 && theirs == last {
+        return -1;
+    }
+    theirs as i8 - mine as i8
+}
+
+// the order of `len` items after shuffling them with the given seed: the item at index N of the
+// result is the index of the item before shuffling
+pub fn shuffled_order(seed: u64, len: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..len).collect();
+    Deck::shuffle_cards(&mut WyRand::new_seed(seed), &mut order);
+    order
+}
+
+// random deal numbers have at most 9 digits, so they fit the info panel
+const RANDOM_DEAL_LIMIT: u64 = 1_000_000_000;
+
+// generate a random deal number
+pub fn random_deal() -> u64 {
+    let mut rng = WyRand::new();
+    rng.generate_range::<u64>(0, RANDOM_DEAL_LIMIT)
+}
+
+// total number of suits in all decks: 4 full decks, 8 two-suit decks, or 16 single-suit ones
+pub const MAX_DECK_SUITS: usize = 16;
+// the maximum number of jokers in a single deck
+pub const MAX_JOKERS: u8 = 2;
+
+// deck composition: the ranks and suits a single deck is built of. A full deck has all 13 ranks
+// and 4 suits. Stripped decks drop some ranks(e.g, Piquet deck has only 7..K and A), and Spider
+// variants use decks made of 1 or 2 suits
+#[derive(Clone, PartialEq, Debug)]
+pub struct DeckKind {
+    pub faces: Vec<Face>, // always sorted from A to K
+    pub suits: Vec<Suit>,
+    pub jokers: u8, // the number of jokers in a deck: the first one is black, the second one is red
+}
+
+impl DeckKind {
+    pub fn full() -> Self {
+        DeckKind::with_faces(&(0..=face_to_i8(Face::K)).map(i8_to_face).collect::<Vec<Face>>())
+    }
+    // 32-card deck: A, 7, 8, 9, 10, J, Q, and K
+    pub fn piquet() -> Self {
+        DeckKind::with_faces(&[Face::A, Face::N7, Face::N8, Face::N9, Face::N10, Face::J, Face::Q, Face::K])
+    }
+    // 36-card deck: A, and 6 to K
+    pub fn stripped36() -> Self {
+        DeckKind::with_faces(&[Face::A, Face::N6, Face::N7, Face::N8, Face::N9, Face::N10, Face::J, Face::Q, Face::K])
+    }
+    // a deck of all suits with the given ranks. Duplicates are dropped
+    pub fn with_faces(faces: &[Face]) -> Self {
+        let suits = (suit_to_i8(Suit::Spade)..=suit_to_i8(Suit::Heart)).map(i8_to_suit).collect();
+        let mut kind = DeckKind { faces: Vec::new(), suits, jokers: 0 };
+        kind.set_faces(faces);
+        kind
+    }
+    pub fn set_faces(&mut self, faces: &[Face]) {
+        let mut fs: Vec<Face> = faces.iter().filter(|f| face_is_rank(**f)).copied().collect();
+        fs.sort_by_key(|f| face_to_i8(*f));
+        fs.dedup();
+        self.faces = fs;
+    }
+    pub fn set_suits(&mut self, suits: &[Suit]) {
+        let mut ss: Vec<Suit> = suits.iter().filter(|s| **s != Suit::Any).copied().collect();
+        ss.sort_by_key(|s| suit_to_i8(*s));
+        ss.dedup();
+        self.suits = ss;
+    }
+    pub fn has_face(&self, face: Face) -> bool {
+        self.faces.contains(&face)
+    }
+    // the number of cards in a single deck
+    pub fn size(&self) -> usize {
+        self.faces.len() * self.suits.len() + usize::from(self.jokers)
+    }
+    // the maximum number of decks that can be used together
+    pub fn max_count(&self) -> u8 {
+        if self.suits.is_empty() {
+            return 0;
+        }
+        (MAX_DECK_SUITS / self.suits.len()) as u8
+    }
+    pub fn validate(&self, count: u8) -> Result<(), SolError> {
+        if self.faces.is_empty() || self.suits.is_empty() {
+            return Err(SolError::EmptyDeck);
+        }
+        if count == 0 || count > self.max_count() {
+            return Err(SolError::InvalidDeckNumber(count, self.max_count()));
+        }
+        if self.jokers > MAX_JOKERS {
+            return Err(SolError::InvalidJokerNumber(self.jokers));
+        }
+        Ok(())
+    }
+}
+
+// returns true for faces of real cards(2 to A)
+pub fn face_is_rank(f: Face) -> bool {
+    !matches!(f, Face::Joker | Face::Empty | Face::Any | Face::Unavail | Face::Column)
+}
+
+#[derive(Clone)]
 pub struct Deck {
     cards: Vec<Card>,
     idx: usize,
+    rng: WyRand,
 }
 
 impl Deck {
-    pub fn new(count: u8) -> Result<Deck, SolError> {
-        if !(1..=2).contains(&count) {
-            return Err(SolError::InvalidDeckNumber(count));
-        }
-        let mut dck: Deck = Deck { cards: Vec::new(), idx: 0 };
+    // creates a shuffled deck. The same seed always produces the same card order
+    pub fn new(count: u8, kind: &DeckKind, seed: u64) -> Result<Deck, SolError> {
+        kind.validate(count)?;
+        let mut dck: Deck = Deck { cards: Vec::new(), idx: 0, rng: WyRand::new_seed(seed) };
         for _d in 0..count {
-            for s in suit_to_i8(Suit::Spade)..=suit_to_i8(Suit::Heart) {
-                for f in 0..=face_to_i8(Face::K) {
-                    dck.cards.push(Card::new(i8_to_suit(s), i8_to_face(f)));
+            for s in kind.suits.iter() {
+                for f in kind.faces.iter() {
+                    dck.cards.push(Card::new(*s, *f));
                 }
             }
+            for j in 0..kind.jokers {
+                let suit = if j % 2 == 0 { Suit::Spade } else { Suit::Heart };
+                dck.cards.push(Card::new(suit, Face::Joker));
+            }
         }
         dck.shuffle();
         Ok(dck)
     }
 
+    // Fisher-Yates shuffle. It generates u64 values instead of usize ones to make the result
+    // independent of the platform
+    fn shuffle_cards<T>(rng: &mut WyRand, cards: &mut [T]) {
+        for idx in (1..cards.len()).rev() {
+            let other = rng.generate_range::<u64>(0, idx as u64 + 1) as usize;
+            cards.swap(idx, other);
+        }
+    }
     fn shuffle(&mut self) {
-        let mut rng = WyRand::new();
-        rng.shuffle(&mut self.cards);
+        Deck::shuffle_cards(&mut self.rng, &mut self.cards);
     }
     pub fn is_empty(&self) -> bool {
         self.idx >= self.cards.len()
@@ -203,8 +440,7 @@ impl Deck {
     }
     pub fn remove_cards(&mut self, cards: &[Card]) {
         let mut c = cards.to_vec();
-        let mut rng = WyRand::new();
-        rng.shuffle(&mut c);
+        Deck::shuffle_cards(&mut self.rng, &mut c);
         let mut from_left = true;
         for card in c.iter() {
             let pos = if from_left {
@@ -244,11 +480,22 @@ mod card_test {
             Df { f: Card::new(Suit::Spade, Face::N3), s: Card::new(Suit::Spade, Face::N6), d: 3 },
         ];
         for df in whats.iter() {
-            let diff = df.f.diff(&df.s);
+            let diff = df.f.diff(&df.s, RankOrder::default());
             assert_eq!(diff, df.d);
-            let diff = df.s.diff(&df.f);
+            let diff = df.s.diff(&df.f, RankOrder::default());
             assert_eq!(diff, -df.d);
         }
+
+        let (ace, two, king) =
+            (Card::new(Suit::Spade, Face::A), Card::new(Suit::Spade, Face::N2), Card::new(Suit::Spade, Face::K));
+        assert_eq!(king.diff(&ace, RankOrder::default()), 1);
+        let no_wrap = RankOrder { wrap: false, ace: Ace::Low };
+        assert_eq!(king.diff(&ace, no_wrap), -12);
+        let high = RankOrder { wrap: false, ace: Ace::High };
+        assert_eq!(king.diff(&ace, high), 1);
+        assert_eq!(two.diff(&ace, high), 12);
+        let high_wrap = RankOrder { wrap: true, ace: Ace::High };
+        assert_eq!(ace.diff(&two, high_wrap), 1);
     }
 
     #[test]
@@ -260,6 +507,78 @@ mod card_test {
         }
     }
 
+    #[test]
+    fn seeded_deck() {
+        let full = DeckKind::full();
+        let mut first = Deck::new(2, &full, 12345).unwrap();
+        let mut second = Deck::new(2, &full, 12345).unwrap();
+        let mut other = Deck::new(2, &full, 54321).unwrap();
+        let mut same_as_other = true;
+        while let Some(c1) = first.deal_card() {
+            let c2 = second.deal_card().unwrap();
+            let c3 = other.deal_card().unwrap();
+            assert_eq!(c1.face, c2.face);
+            assert_eq!(c1.suit, c2.suit);
+            if c1.face != c3.face || c1.suit != c3.suit {
+                same_as_other = false;
+            }
+        }
+        assert!(second.is_empty());
+        assert!(!same_as_other);
+        assert!((0..100).all(|_| random_deal() < RANDOM_DEAL_LIMIT));
+    }
+
+    #[test]
+    fn stripped_deck() {
+        let piquet = DeckKind::piquet();
+        assert_eq!(piquet.size(), 32);
+        let mut deck = Deck::new(1, &piquet, 12345).unwrap();
+        let mut cnt = 0;
+        while let Some(c) = deck.deal_card() {
+            assert!(piquet.has_face(c.face));
+            cnt += 1;
+        }
+        assert_eq!(cnt, 32);
+        assert!(Deck::new(5, &DeckKind::full(), 1).is_err());
+
+        let mut spider = DeckKind::full();
+        spider.set_suits(&[Suit::Spade]);
+        assert_eq!(spider.max_count(), 16);
+        assert!(Deck::new(8, &spider, 1).is_ok());
+
+        let ace = Card::new(Suit::Spade, Face::A);
+        let six = Card::new(Suit::Spade, Face::N6);
+        let king = Card::new(Suit::Spade, Face::K);
+        let faces = DeckKind::stripped36().faces;
+        let order = RankOrder::default();
+        assert_eq!(ace.diff_in(&six, &faces, order), 1);
+        assert_eq!(six.diff_in(&ace, &faces, order), -1);
+        assert_eq!(king.diff_in(&ace, &faces, order), 1);
+        assert_eq!(ace.diff_in(&king, &faces, order), -1);
+        let high = RankOrder { wrap: false, ace: Ace::High };
+        assert_eq!(king.diff_in(&ace, &faces, high), 1);
+        assert_eq!(ace.diff_in(&six, &faces, high), -8);
+    }
+
+    #[test]
+    fn card_codes() {
+        let mut cards = vec![Card::new(Suit::Heart, Face::N10), Card::new(Suit::Spade, Face::A)];
+        cards[0].up = true;
+        let s = cards_to_str(&cards);
+        assert_eq!(s, "10h *As".to_string());
+        let back = str_to_cards(&s).unwrap();
+        assert_eq!(back.len(), 2);
+        for (c1, c2) in cards.iter().zip(back.iter()) {
+            assert_eq!(c1.face, c2.face);
+            assert_eq!(c1.suit, c2.suit);
+            assert_eq!(c1.up, c2.up);
+        }
+        assert!(str_to_cards("").unwrap().is_empty());
+        assert!(str_to_card("1h").is_err());
+        assert!(str_to_card("Ax").is_err());
+        assert!(str_to_card("anys").is_err());
+    }
+
     #[test]
     fn suiti8() {
         for suit_id in 0i8..3i8 {
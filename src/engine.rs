@@ -1,16 +1,24 @@
 use log::info;
-use std::convert::From;
-
-use crate::card::{Card, Deck, Face, Suit};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::convert::{From, TryFrom};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::card::{cards_to_str, random_deal, shuffled_order, str_to_cards, Ace, Card, Deck, Face, RankOrder, Suit};
 use crate::err::SolError;
-use crate::gconf::{Conf, FaceOrder, Playable, SuitOrder};
+use crate::gconf::{Collect, Conf, DealCols, FaceOrder, Goal, Layout, Playable, RedealMode, SuitOrder};
 
 pub const ANY_COL: usize = 9999;
+// points for every card in foundations
+const FND_CARD_SCORE: i32 = 10;
 
-type UndoList = Vec<Undo>;
+type UndoList = VecDeque<Undo>;
 type CardList = Vec<Card>;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub struct SlotConf {
     // pile can be selected (false only for deck pile)
     pub selectable: bool,
@@ -108,6 +116,66 @@ impl SlotConf {
             limit: 0,
         }
     }
+    // default settings for a reserve pile
+    pub fn new_for_reserve() -> Self {
+        SlotConf {
+            selectable: true,
+            all_up: false,
+            refill: false,
+            start_face: Face::Any,
+            start_suit: Suit::Any,
+            suit_order: SuitOrder::Any,
+            face_order: FaceOrder::Any,
+            playable: Playable::Top,
+            flip: true,
+            take_only: true,
+            draw_all: false,
+            limit: 0,
+        }
+    }
+}
+
+// returns true if the card can be put on top of another card in a pile with a given configuration.
+// Ranks follow the game rules: a stripped deck skips missing ones, and an ace can be high
+fn fits_on(conf: &SlotConf, rules: &Conf, card: &Card, top: &Card) -> bool {
+    fits_over(conf, rules, card, top, 0)
+}
+
+// the same as `fits_on` but there are `gap` jokers between the cards. A joker takes the place of
+// the card it substitutes, so the card must be `gap + 1` ranks away from the one below the jokers
+fn fits_over(conf: &SlotConf, rules: &Conf, card: &Card, below: &Card, gap: usize) -> bool {
+    if card.is_joker() || below.is_joker() {
+        return true;
+    }
+    let odd = gap % 2 == 1;
+    let ok_suit = match conf.suit_order {
+        SuitOrder::SameSuit => card.is_same_suit(below),
+        SuitOrder::ExceptSame => gap != 0 || !card.is_same_suit(below),
+        SuitOrder::SameColor => card.is_same_color(below),
+        SuitOrder::AlternateColor => card.is_same_color(below) == odd,
+        SuitOrder::Any => true,
+        SuitOrder::Forbid => false,
+    };
+    let step = gap as i8 + 1;
+    let diff = card.diff_in(below, &rules.deck.faces, rules.rank_order);
+    let ok_face = match conf.face_order {
+        FaceOrder::Asc => diff == -step,
+        FaceOrder::Desc => diff == step,
+        // every joker can go either up or down
+        FaceOrder::Any => diff.abs() <= step && (step - diff.abs()) % 2 == 0,
+    };
+    ok_suit && ok_face
+}
+
+// returns true if the moved cards can be put on top of the pile. Jokers at the bottom of the moved
+// cards and at the top of the pile are skipped and counted as the cards they substitute
+fn fits_stack(conf: &SlotConf, rules: &Conf, moved: &[Card], pile: &[Card]) -> bool {
+    let moved_gap = moved.iter().take_while(|c| c.is_joker()).count();
+    let pile_gap = pile.iter().rev().take_while(|c| c.is_joker()).count();
+    if moved_gap == moved.len() || pile_gap == pile.len() {
+        return true;
+    }
+    fits_over(conf, rules, &moved[moved_gap], &pile[pile.len() - 1 - pile_gap], moved_gap + pile_gap)
 }
 
 #[derive(Clone)]
@@ -116,11 +184,149 @@ pub struct Pile {
     pub cards: CardList,
 }
 
-// game snapshot
+// a single change of a pile made by a move
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Change {
+    Move { from: usize, to: usize, count: usize }, // top cards moved to another pile keeping their order
+    Flip(usize),                                   // the top card of a pile turned over
+    Reverse(usize),                                // the order of cards in a pile reversed
+    Shuffle { pile: usize, seed: u64 },            // cards of a pile shuffled with the given seed
+}
+
+// changes made by a move and the game state before it
+#[derive(Clone)]
 struct Undo {
     redeals: i8,
-    piles: Vec<CardList>,
     selected: Pos,
+    log_len: usize,
+    changes: Vec<Change>,
+}
+
+// game snapshot in a form suitable for saving to a file. The current game state keeps cards of
+// all piles, undo and redo entries keep only the changes made by a move.
+// Note: TOML requires all plain values to go before tables, so do not change the field order
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedSnapshot {
+    pub redeals: i8,
+    pub piles: Vec<String>,   // cards of every pile, see `cards_to_str`
+    pub changes: Vec<String>, // changes made by a move, see `change_to_str`
+    #[serde(default)]
+    pub log_len: usize, // the number of moves made
+    pub selected: Pos,
+}
+
+// the full game state in a form suitable for saving to a file
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub seed: u64,
+    pub fnd_start: Vec<Face>, // foundation first card faces (they may be chosen at deal)
+    #[serde(default)]
+    pub log: Vec<String>, // all moves including the ones rolled back by undo, see `entry_to_str`
+    pub state: SavedSnapshot,
+    // TOML writes an empty list as a plain value, and it cannot follow the `state` table
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub undo: Vec<SavedSnapshot>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redo: Vec<SavedSnapshot>,
+}
+
+// a single player's action
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Move {
+    // move `count` top cards from one pile to another one. Piles are identified by their IDs
+    Card { from: usize, to: usize, count: usize },
+    // remove the top cards of two piles together. Piles are identified by their IDs
+    Pair { a: usize, b: usize },
+    Deal,   // deal cards from the deck
+    Redeal, // move all cards from the waste back to the empty deck
+}
+
+// a move made by the player and the game time when it happened
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LogEntry {
+    pub time: Duration,
+    pub mv: Move,
+}
+
+// convert a log entry to a text line: time in seconds followed by the move.
+// Examples: "1.520 move 5 3 1"(from pile 5 to pile 3, 1 card), "2.000 pair 4 9", "3.000 deal",
+// "3.100 redeal"
+pub fn entry_to_str(entry: &LogEntry) -> String {
+    let tm = format!("{}.{:03}", entry.time.as_secs(), entry.time.subsec_millis());
+    match entry.mv {
+        Move::Card { from, to, count } => format!("{} move {} {} {}", tm, from, to, count),
+        Move::Pair { a, b } => format!("{} pair {} {}", tm, a, b),
+        Move::Deal => format!("{} deal", tm),
+        Move::Redeal => format!("{} redeal", tm),
+    }
+}
+
+// parse a log entry created with `entry_to_str`
+pub fn str_to_entry(s: &str) -> Result<LogEntry, SolError> {
+    let err = || SolError::InvalidMoveEntry(s.to_string());
+    let words: Vec<&str> = s.split_whitespace().collect();
+    if words.len() < 2 {
+        return Err(err());
+    }
+    let secs = match words[0].parse::<f64>() {
+        Ok(n) if n >= 0.0 => n,
+        _ => return Err(err()),
+    };
+    let time = Duration::from_millis((secs * 1000.0).round() as u64);
+    let mv = match (words[1], words.len()) {
+        ("deal", 2) => Move::Deal,
+        ("redeal", 2) => Move::Redeal,
+        ("move", 5) => {
+            let mut nums = [0usize; 3];
+            for (idx, w) in words[2..].iter().enumerate() {
+                nums[idx] = w.parse::<usize>().map_err(|_| err())?;
+            }
+            Move::Card { from: nums[0], to: nums[1], count: nums[2] }
+        }
+        ("pair", 4) => {
+            let a = words[2].parse::<usize>().map_err(|_| err())?;
+            let b = words[3].parse::<usize>().map_err(|_| err())?;
+            Move::Pair { a, b }
+        }
+        _ => return Err(err()),
+    };
+    Ok(LogEntry { time, mv })
+}
+
+// convert a change made by a move to a text: "move 5 3 2"(2 top cards from pile 5 to pile 3),
+// "flip 4"(the top card of pile 4 turned over), "reverse 6", "shuffle 6 1234"(pile 6 shuffled with seed 1234)
+fn change_to_str(change: Change) -> String {
+    match change {
+        Change::Move { from, to, count } => format!("move {} {} {}", from, to, count),
+        Change::Flip(pile) => format!("flip {}", pile),
+        Change::Reverse(pile) => format!("reverse {}", pile),
+        Change::Shuffle { pile, seed } => format!("shuffle {} {}", pile, seed),
+    }
+}
+
+// parse a change created with `change_to_str`
+fn str_to_change(s: &str) -> Result<Change, SolError> {
+    let mut nums = Vec::new();
+    let mut words = s.split_whitespace();
+    let kind = words.next().unwrap_or("");
+    for w in words {
+        nums.push(w.parse::<u64>().map_err(|_| SolError::InvalidSavedGame)?);
+    }
+    let pile = |n: u64| usize::try_from(n).map_err(|_| SolError::InvalidSavedGame);
+    match (kind, nums.len()) {
+        ("move", 3) => Ok(Change::Move { from: pile(nums[0])?, to: pile(nums[1])?, count: pile(nums[2])? }),
+        ("flip", 1) => Ok(Change::Flip(pile(nums[0])?)),
+        ("reverse", 1) => Ok(Change::Reverse(pile(nums[0])?)),
+        ("shuffle", 2) => Ok(Change::Shuffle { pile: pile(nums[0])?, seed: nums[1] }),
+        _ => Err(SolError::InvalidSavedGame),
+    }
+}
+
+// why the deck cannot deal cards to columns
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DealBlock {
+    EmptyCol, // a column is empty while the rules require all columns filled
+    NoTarget, // no column can get a card, e.g. the deck deals only to empty columns and none is empty
 }
 
 // cursor movement direction
@@ -134,10 +340,11 @@ pub enum Direction {
     Waste,
     Pile,
     Temp,
+    Reserve,
 }
 
 // cursor position in a play area
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Pos {
     pub col: usize,
     pub row: usize,
@@ -158,52 +365,199 @@ impl Pos {
     }
 }
 
-pub struct Game<'a> {
-    conf: &'a Conf,     // selected solitaire rules
+#[derive(Clone)]
+pub struct Game {
+    conf: Arc<Conf>,    // selected solitaire rules
     deck: Deck,         // a deck
     selected: Pos,      // position of the cursor in play area
-    undo: UndoList,     // list of game snapshots
+    undo: UndoList,     // list of moves to roll back
+    redo: UndoList,     // list of moves rolled back by undo
+    undo_limit: usize,  // maximum length of undo list (0 - unlimited)
     piles: Vec<Pile>,   // order: fnd, cols, temp, pile
     initial: Vec<Card>, // list of intial card of foundation piies (for example see Alhambra)
     redeals: i8,        // redeals left
+    seed: u64,          // deal number
+    log: Vec<LogEntry>, // all moves made. It includes the moves rolled back by undo to redo them
+    log_len: usize,     // the number of moves made. Entries after it are the ones rolled back by undo
+    clock: Duration,    // the current game time to mark moves in the log
 }
 
-impl<'a> Game<'a> {
-    pub fn init(conf: &'a Conf) -> Result<Game<'a>, SolError> {
-        let deck = Deck::new(conf.deck_count)?;
+impl Game {
+    // start a new game. The same deal number always produces the same layout for the same rules.
+    // If the deal number is not defined, a random one is generated
+    pub fn init<C: Into<Arc<Conf>>>(conf: C, seed: Option<u64>) -> Result<Game, SolError> {
+        let conf = conf.into();
+        let seed = seed.unwrap_or_else(random_deal);
+        let deck = Deck::new(conf.deck_count, &conf.deck, seed)?;
         let redeals = conf.redeals();
         let mut g = Game {
             conf,
             deck,
-            undo: Vec::new(),
+            seed,
+            undo: VecDeque::new(),
+            redo: VecDeque::new(),
+            undo_limit: 0,
             piles: Vec::new(),
             initial: Vec::new(),
             selected: Pos::new(),
             redeals,
+            log: Vec::new(),
+            log_len: 0,
+            clock: Duration::new(0, 0),
         };
         g.init_cols()?;
         // at start the current card is always the first one in the first column
         g.selected = Pos { col: g.first_col().unwrap(), row: 0 };
+        if g.is_hidden(g.selected.col) {
+            g.move_selection(Direction::Right);
+        }
+        Ok(g)
+    }
+
+    // restore a game saved with `save`
+    pub fn restore<C: Into<Arc<Conf>>>(conf: C, saved: &SavedGame) -> Result<Game, SolError> {
+        let conf = conf.into();
+        let deck = Deck::new(conf.deck_count, &conf.deck, saved.seed)?;
+        let redeals = conf.redeals();
+        let mut g = Game {
+            conf,
+            deck,
+            seed: saved.seed,
+            undo: VecDeque::new(),
+            redo: VecDeque::new(),
+            undo_limit: 0,
+            piles: Vec::new(),
+            initial: Vec::new(),
+            selected: Pos::new(),
+            redeals,
+            log: Vec::new(),
+            log_len: 0,
+            clock: Duration::new(0, 0),
+        };
+        g.init_piles();
+        if saved.fnd_start.len() != g.fnd_count() {
+            return Err(SolError::InvalidSavedGame);
+        }
+        let first = g.first_fnd().unwrap();
+        for (idx, face) in saved.fnd_start.iter().enumerate() {
+            g.piles[first + idx].conf.start_face = *face;
+        }
+        for entry in saved.log.iter() {
+            g.log.push(str_to_entry(entry)?);
+        }
+        g.load_state(&saved.state)?;
+        for snap in saved.undo.iter() {
+            let undo = g.load_snapshot(snap)?;
+            g.undo.push_back(undo);
+        }
+        for snap in saved.redo.iter() {
+            let redo = g.load_snapshot(snap)?;
+            g.redo.push_back(redo);
+        }
         Ok(g)
     }
 
+    // return the full game state, including undo history
+    pub fn save(&self) -> SavedGame {
+        let first = self.first_fnd().unwrap();
+        let fnd_start = (first..first + self.fnd_count()).map(|idx| self.piles[idx].conf.start_face).collect();
+        SavedGame {
+            seed: self.seed,
+            fnd_start,
+            log: self.log.iter().map(entry_to_str).collect(),
+            state: SavedSnapshot {
+                redeals: self.redeals,
+                piles: self.piles.iter().map(|p| cards_to_str(&p.cards)).collect(),
+                changes: Vec::new(),
+                log_len: self.log_len,
+                selected: self.selected,
+            },
+            undo: self.undo.iter().map(Game::save_snapshot).collect(),
+            redo: self.redo.iter().map(Game::save_snapshot).collect(),
+        }
+    }
+
+    fn save_snapshot(snap: &Undo) -> SavedSnapshot {
+        SavedSnapshot {
+            redeals: snap.redeals,
+            selected: snap.selected,
+            log_len: snap.log_len,
+            piles: Vec::new(),
+            changes: snap.changes.iter().map(|c| change_to_str(*c)).collect(),
+        }
+    }
+
+    fn load_state(&mut self, snap: &SavedSnapshot) -> Result<(), SolError> {
+        if snap.piles.len() != self.piles.len()
+            || snap.selected.col >= self.piles.len()
+            || snap.log_len > self.log.len()
+        {
+            return Err(SolError::InvalidSavedGame);
+        }
+        for (idx, pile) in snap.piles.iter().enumerate() {
+            let cards = str_to_cards(pile)?;
+            // the layout of piles may change with rules, e.g. when a column turns into a reserve
+            let conf = &self.piles[idx].conf;
+            let face_down = !conf.selectable && !conf.all_up;
+            if (conf.all_up || face_down) && cards.iter().any(|c| c.is_up() == face_down) {
+                return Err(SolError::InvalidSavedGame);
+            }
+            self.piles[idx].cards = cards;
+        }
+        self.redeals = snap.redeals;
+        self.selected = snap.selected;
+        self.log_len = snap.log_len;
+        Ok(())
+    }
+
+    fn load_snapshot(&self, snap: &SavedSnapshot) -> Result<Undo, SolError> {
+        if snap.selected.col >= self.piles.len() || snap.log_len > self.log.len() {
+            return Err(SolError::InvalidSavedGame);
+        }
+        let mut changes = Vec::new();
+        for c in snap.changes.iter() {
+            let change = str_to_change(c)?;
+            let bad = match change {
+                Change::Move { from, to, count } => from >= self.piles.len() || to >= self.piles.len() || count == 0,
+                Change::Flip(pile) | Change::Reverse(pile) | Change::Shuffle { pile, .. } => pile >= self.piles.len(),
+            };
+            if bad {
+                return Err(SolError::InvalidSavedGame);
+            }
+            changes.push(change);
+        }
+        Ok(Undo { redeals: snap.redeals, selected: snap.selected, log_len: snap.log_len, changes })
+    }
+
     pub fn redeal_left(&self) -> i8 {
         self.redeals
     }
 
+    // the number of the current deal
+    pub fn deal_number(&self) -> u64 {
+        self.seed
+    }
+
     // can the position be selected with cursor (can the card be played during this move)
     pub fn is_selectable(&self, pos: Option<Pos>) -> bool {
         let pos = if let Some(p) = pos { p } else { self.selected };
         if pos.col >= self.piles.len() {
             false
         } else {
-            if self.conf.playable == Playable::Top && pos.row != 0 {
+            // a column may have its own rules, and other piles give away only their top cards
+            let conf = &self.piles[pos.col].conf;
+            let playable = conf.playable;
+            if (playable == Playable::Top || conf.take_only) && pos.row != 0 {
+                return false;
+            }
+            let ordered = matches!(playable, Playable::Ordered | Playable::SameSuit | Playable::Supermove);
+            if ordered && pos.row >= self.ordered_count(pos.col) {
                 return false;
             }
-            if self.conf.playable == Playable::Ordered && pos.row >= self.ordered_count(pos.col) {
+            if !self.piles[pos.col].conf.selectable || self.is_covered(pos.col) {
                 return false;
             }
-            if !self.piles[pos.col].conf.selectable {
+            if self.is_fnd(pos.col) && (pos.row != 0 || !self.can_take_back(pos.col)) {
                 return false;
             }
             let l = self.piles[pos.col].cards.len();
@@ -247,29 +601,34 @@ impl<'a> Game<'a> {
         if pile.cards.len() < 2 {
             return pile.cards.len();
         }
+        let same_suit = pile.conf.playable == Playable::SameSuit;
         let mut cnt = 1usize;
         let mut idx = pile.cards.len() - 1;
+        // the closest regular card above and the number of jokers between it and the current card
+        let mut upper = if pile.cards[idx].is_joker() { None } else { Some(&pile.cards[idx]) };
+        let mut gap = 0usize;
         while idx > 0 {
-            let ok_suit = match pile.conf.suit_order {
-                SuitOrder::SameSuit => pile.cards[idx].is_same_suit(&pile.cards[idx - 1]),
-                SuitOrder::ExceptSame => !pile.cards[idx].is_same_suit(&pile.cards[idx - 1]),
-                SuitOrder::SameColor => pile.cards[idx].is_same_color(&pile.cards[idx - 1]),
-                SuitOrder::AlternateColor => !pile.cards[idx].is_same_color(&pile.cards[idx - 1]),
-                SuitOrder::Any => true,
-                SuitOrder::Forbid => false,
-            };
-            let diff = pile.cards[idx].diff(&pile.cards[idx - 1]);
-            let ok_face = match pile.conf.face_order {
-                FaceOrder::Asc => diff == -1,
-                FaceOrder::Desc => diff == 1,
-                FaceOrder::Any => diff == 1 || diff == -1,
-            };
-            if ok_suit && ok_face {
-                cnt += 1;
-                idx -= 1;
+            let below = &pile.cards[idx - 1];
+            if below.is_joker() {
+                if !below.is_up() {
+                    break;
+                }
+                gap += 1;
             } else {
-                break;
+                let fits = match upper {
+                    None => true,
+                    Some(card) => {
+                        fits_over(&pile.conf, &self.conf, card, below, gap) && (!same_suit || card.is_same_suit(below))
+                    }
+                };
+                if !fits {
+                    break;
+                }
+                upper = Some(below);
+                gap = 0;
             }
+            cnt += 1;
+            idx -= 1;
         }
         cnt
     }
@@ -278,7 +637,17 @@ impl<'a> Game<'a> {
     // be moved to the top of pile with ID pile_id
     fn can_move(&self, pos: Pos, pile_id: usize) -> bool {
         let card = self.card_at(pos);
-        if !card.up || card.is_empty() || pile_id >= self.piles.len() {
+        if !card.up || card.is_empty() || pile_id >= self.piles.len() || self.is_covered(pos.col) {
+            return false;
+        }
+        if self.is_pairing() {
+            return self.can_pair(pos, pile_id);
+        }
+        if self.is_fnd(pos.col) && (self.is_fnd(pile_id) || pos.row != 0 || !self.can_take_back(pos.col)) {
+            return false;
+        }
+        if self.conf.collect == Collect::Sequence && self.is_fnd(pile_id) {
+            // only full runs go to foundations and they are moved automatically
             return false;
         }
         let pile = &self.piles[pile_id];
@@ -291,29 +660,353 @@ impl<'a> Game<'a> {
         if pile.conf.take_only || (!pile.conf.refill && pile.cards.is_empty()) {
             return false;
         }
+        if self.piles[pos.col].conf.playable == Playable::Supermove && pos.row >= self.move_capacity(pile_id) {
+            return false;
+        }
 
-        if pile.cards.is_empty() {
+        let mut base: &[Card] = &pile.cards;
+        if self.is_fnd(pile_id) {
+            // jokers are only discarded to foundations: cards are built on the top regular card
+            if card.is_joker() {
+                return true;
+            }
+            base = &base[..base.iter().rposition(|c| !c.is_joker()).map_or(0, |i| i + 1)];
+        }
+        if base.is_empty() {
             let ok_suit = pile.conf.start_suit == Suit::Any || pile.conf.start_suit == card.suit;
             let ok_face = pile.conf.start_face == Face::Any || pile.conf.start_face == card.face;
-            return ok_suit && ok_face;
+            return card.is_joker() || (ok_suit && ok_face);
+        }
+
+        let src = &self.piles[pos.col].cards;
+        fits_stack(&pile.conf, &self.conf, &src[src.len() - 1 - pos.row..], base)
+    }
+
+    // the longest run that can be moved at once to pile `dest` by moving cards one by one through
+    // free cells and empty columns. The destination column does not count even if it is empty
+    fn move_capacity(&self, dest: usize) -> usize {
+        let is_free = |id: usize, p: &Pile| id != dest && p.cards.is_empty() && p.conf.refill && !p.conf.take_only;
+        let free = self.piles.iter().enumerate().filter(|(id, p)| self.is_temp(*id) && is_free(*id, p)).count();
+        let empty = self.piles.iter().enumerate().filter(|(id, p)| self.is_col(*id) && is_free(*id, p)).count();
+        (free + 1) << empty.min(16)
+    }
+
+    // returns true if the top card of a foundation can be moved back to the tableau. A card that
+    // fills a foundation at start always stays there
+    fn can_take_back(&self, pile_id: usize) -> bool {
+        if !self.conf.fnd_playable || self.conf.collect != Collect::Card {
+            return false;
+        }
+        let fnd = pile_id - self.first_fnd().unwrap();
+        let fixed = usize::from(self.conf.fnd[fnd].filler.is_some());
+        self.piles[pile_id].cards.len() > fixed
+    }
+
+    // returns true if the rules remove cards by pairs instead of building foundations
+    pub(crate) fn is_pairing(&self) -> bool {
+        matches!(self.conf.collect, Collect::Sum(_) | Collect::SameFace)
+    }
+
+    // returns true if two cards can be removed together
+    fn is_pair(&self, a: &Card, b: &Card) -> bool {
+        match self.conf.collect {
+            Collect::Sum(n) => a.value() + b.value() == n,
+            Collect::SameFace => a.face == b.face,
+            _ => false,
+        }
+    }
+
+    // returns true if the top card of a pile can be removed: it is face-up and not covered
+    fn is_pair_ready(&self, pile_id: usize) -> bool {
+        let pile = &self.piles[pile_id];
+        if self.is_fnd(pile_id) || !pile.conf.selectable || self.is_covered(pile_id) {
+            return false;
+        }
+        match pile.cards.last() {
+            Some(card) => card.is_up(),
+            None => false,
+        }
+    }
+
+    // returns true if the card at position pos can be removed together with the top card of pile
+    // with ID pile_id, or it can be removed alone if pile_id is the first foundation
+    fn can_pair(&self, pos: Pos, pile_id: usize) -> bool {
+        if pos.row != 0 || pos.col == pile_id || !self.is_pair_ready(pos.col) {
+            return false;
+        }
+        let card = self.card_at(pos);
+        if self.is_fnd(pile_id) {
+            let alone = matches!(self.conf.collect, Collect::Sum(n) if card.value() == n);
+            return alone && Some(pile_id) == self.first_fnd();
+        }
+        if !self.is_pair_ready(pile_id) || !self.are_neighbors(pos.col, pile_id) {
+            return false;
+        }
+        self.is_pair(&card, &self.card_at(Pos { col: pile_id, row: 0 }))
+    }
+
+    // returns true if a column of a free layout lies under any non-empty column. Columns of
+    // other layouts are never covered
+    pub fn is_covered(&self, pile_id: usize) -> bool {
+        let places = match self.conf.layout {
+            Layout::Free(ref places) => places,
+            _ => return false,
+        };
+        if !self.is_col(pile_id) {
+            return false;
+        }
+        let first = self.first_col().unwrap();
+        places[pile_id - first].covered_by.iter().any(|&c| !self.piles[first + c].cards.is_empty())
+    }
+
+    // turn face-up the top cards of all columns of a free layout that are not covered anymore
+    fn reveal_uncovered(&mut self) {
+        if !matches!(self.conf.layout, Layout::Free(_)) {
+            return;
+        }
+        let first = self.first_col().unwrap();
+        for col in first..first + self.col_count() {
+            let down = matches!(self.piles[col].cards.last(), Some(card) if !card.is_up());
+            if down && !self.is_covered(col) {
+                self.turn_top(col, true);
+            }
+        }
+    }
+
+    // returns true if the cards of two piles can be paired by their placement: in a grid only
+    // neighbors(including diagonal ones) make a pair, in other layouts any cards do
+    fn are_neighbors(&self, a: usize, b: usize) -> bool {
+        let w = match self.conf.layout {
+            Layout::Grid(w) => usize::from(w),
+            _ => return true,
+        };
+        if !self.is_col(a) || !self.is_col(b) {
+            return true;
+        }
+        let first = self.first_col().unwrap();
+        let (a, b) = (a - first, b - first);
+        (a / w).abs_diff(b / w) <= 1 && (a % w).abs_diff(b % w) <= 1
+    }
+
+    pub(crate) fn is_fnd(&self, pile_id: usize) -> bool {
+        let first = self.first_fnd().unwrap();
+        pile_id >= first && pile_id < first + self.fnd_count()
+    }
+
+    pub(crate) fn is_temp(&self, pile_id: usize) -> bool {
+        match self.first_temp() {
+            Some(first) => pile_id >= first && pile_id < first + self.temp_count(),
+            None => false,
+        }
+    }
+
+    pub(crate) fn is_col(&self, pile_id: usize) -> bool {
+        match self.first_col() {
+            Some(first) => pile_id >= first && pile_id < first + self.col_count(),
+            None => false,
+        }
+    }
+
+    // returns true if no card left in play can be put on top of the card in any column. So,
+    // moving the card to a foundation never blocks the game
+    fn is_safe_for_fnd(&self, card: &Card) -> bool {
+        let col_first = self.first_col().unwrap();
+        for col in col_first..col_first + self.col_count() {
+            let conf = &self.piles[col].conf;
+            if conf.take_only {
+                continue;
+            }
+            for (idx, pile) in self.piles.iter().enumerate() {
+                if self.is_fnd(idx) {
+                    continue;
+                }
+                // a joker can take the place of any card, so it does not need this one
+                if pile.cards.iter().any(|c| !c.is_joker() && fits_on(conf, &self.conf, c, card)) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    // return a card that can be moved to a foundation without any risk to block the game and
+    // the foundation where to put it. A card is safe to move if it starts a foundation, or if
+    // no card left in play can be put on it in columns
+    pub fn safe_move(&self) -> Option<(Pos, Pos)> {
+        if self.deals_to_fnd() {
+            // the deck puts cards to the foundation, so any card moved there may block the game
+            return None;
+        }
+        let first_fnd = self.first_fnd().unwrap();
+        for (idx, pile) in self.piles.iter().enumerate() {
+            if self.is_fnd(idx) || pile.cards.is_empty() || !pile.conf.selectable {
+                continue;
+            }
+            let from = Pos { col: idx, row: 0 };
+            let card = self.card_at(from);
+            if !card.is_up() {
+                continue;
+            }
+            for fnd in first_fnd..first_fnd + self.fnd_count() {
+                if !self.can_move(from, fnd) {
+                    continue;
+                }
+                let fnd_pile = &self.piles[fnd];
+                let starts = fnd_pile.cards.is_empty() && fnd_pile.conf.start_face != Face::Any && !card.is_joker();
+                if starts || self.is_safe_for_fnd(&card) {
+                    return Some((from, Pos { col: fnd, row: 0 }));
+                }
+            }
         }
+        None
+    }
+
+    // return any top card that can be moved to a foundation and the foundation where to put it
+    fn fnd_move(&self) -> Option<(Pos, Pos)> {
+        let first_fnd = self.first_fnd().unwrap();
+        for (idx, pile) in self.piles.iter().enumerate() {
+            if self.is_fnd(idx) || pile.cards.is_empty() {
+                continue;
+            }
+            let from = Pos { col: idx, row: 0 };
+            for fnd in first_fnd..first_fnd + self.fnd_count() {
+                if self.can_move(from, fnd) {
+                    return Some((from, Pos { col: fnd, row: 0 }));
+                }
+            }
+        }
+        None
+    }
+
+    // copy of the game without undo history to look ahead
+    pub fn fork(&self) -> Game {
+        Game {
+            conf: Arc::clone(&self.conf),
+            deck: self.deck.clone(),
+            selected: self.selected,
+            undo: VecDeque::new(),
+            redo: VecDeque::new(),
+            undo_limit: self.undo_limit,
+            piles: self.piles.clone(),
+            initial: self.initial.clone(),
+            redeals: self.redeals,
+            seed: self.seed,
+            log: Vec::new(),
+            log_len: 0,
+            clock: self.clock,
+        }
+    }
 
-        let top = &pile.cards[pile.cards.len() - 1];
-        let ok_suit = match pile.conf.suit_order {
-            SuitOrder::SameSuit => card.is_same_suit(top),
-            SuitOrder::ExceptSame => !card.is_same_suit(top),
-            SuitOrder::SameColor => card.is_same_color(top),
-            SuitOrder::AlternateColor => !card.is_same_color(top),
-            SuitOrder::Any => true,
-            SuitOrder::Forbid => false,
+    // hash of all cards in all piles and the number of redeals left. Equal game positions always
+    // have the same hash. Free cells are interchangeable, so their order does not change the hash.
+    // The same is true for columns unless the deck deals cards to them or their placement matters.
+    // Piles with different rules are not interchangeable, so the rules are a part of a pile hash
+    pub(crate) fn state_hash(&self) -> u64 {
+        let pile_hash = |pile: &Pile| {
+            let mut hasher = DefaultHasher::new();
+            let conf = &pile.conf;
+            (conf.take_only, conf.refill, conf.start_face, conf.limit).hash(&mut hasher);
+            (conf.face_order, conf.suit_order, conf.playable).hash(&mut hasher);
+            for card in pile.cards.iter() {
+                card.face.hash(&mut hasher);
+                card.suit.hash(&mut hasher);
+                card.up.hash(&mut hasher);
+            }
+            hasher.finish()
         };
-        let diff = card.diff(top);
-        let ok_face = match pile.conf.face_order {
-            FaceOrder::Asc => diff == -1,
-            FaceOrder::Desc => diff == 1,
-            FaceOrder::Any => diff == 1 || diff == -1,
+        let mut temps: Vec<u64> = Vec::new();
+        let mut cols: Vec<u64> = Vec::new();
+        let mut hasher = DefaultHasher::new();
+        let ordered_cols = match self.conf.pile {
+            Some(ref pconf) => pconf.pile_to_cols,
+            None => false,
+        } || self.conf.layout != Layout::Columns;
+        // unlimited redeals counter keeps decreasing but only the first pass differs from the next ones
+        let redeals = if self.redeals >= 0 {
+            self.redeals
+        } else if self.redeals == self.conf.redeals() {
+            -1
+        } else {
+            -2
         };
-        ok_face && ok_suit
+        redeals.hash(&mut hasher);
+        for (idx, pile) in self.piles.iter().enumerate() {
+            let h = pile_hash(pile);
+            if self.is_temp(idx) {
+                temps.push(h);
+            } else if self.is_col(idx) && !ordered_cols {
+                cols.push(h);
+            } else {
+                h.hash(&mut hasher);
+            }
+        }
+        temps.sort_unstable();
+        cols.sort_unstable();
+        temps.hash(&mut hasher);
+        cols.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // returns true if the game is not won and the player cannot do anything: no card can be
+    // moved anywhere, and the deck cannot be dealt(it is empty and no redeal is left)
+    pub fn is_dead_end(&self) -> bool {
+        !self.is_completed() && self.legal_moves().is_empty()
+    }
+
+    // returns true if the game is not won yet, all cards are face-up, the deck and waste are
+    // empty, and the game can be finished by moving top cards to foundations only
+    pub fn can_autocomplete(&self) -> bool {
+        if self.is_completed() {
+            return false;
+        }
+        if let Some(first) = self.first_pile() {
+            for idx in first..first + self.pile_count() {
+                if !self.piles[idx].cards.is_empty() {
+                    return false;
+                }
+            }
+        }
+        for (idx, pile) in self.piles.iter().enumerate() {
+            if !self.is_fnd(idx) && pile.cards.iter().any(|c| !c.is_up()) {
+                return false;
+            }
+        }
+        let mut g = self.fork();
+        while let Some((from, to)) = g.fnd_move() {
+            if g.move_card(from, to).is_err() {
+                return false;
+            }
+        }
+        g.is_completed()
+    }
+
+    // finish the game if it is trivially won(see `can_autocomplete`). Returns false if the game
+    // cannot be finished automatically
+    pub fn autocomplete(&mut self) -> bool {
+        if !self.can_autocomplete() {
+            return false;
+        }
+        while let Some((from, to)) = self.fnd_move() {
+            if self.move_card(from, to).is_err() {
+                break;
+            }
+        }
+        self.is_completed()
+    }
+
+    // move all cards that are safe to move to foundations. Every move gets its own snapshot,
+    // so they can be undone one by one. Returns the number of moved cards
+    pub fn autoplay(&mut self) -> usize {
+        let mut cnt = 0;
+        while let Some((from, to)) = self.safe_move() {
+            self.take_snapshot();
+            if self.move_card(from, to).is_err() {
+                self.undo.pop_back();
+                break;
+            }
+            cnt += 1;
+        }
+        cnt
     }
 
     // return the currently selected card
@@ -326,6 +1019,11 @@ impl<'a> Game<'a> {
         self.conf.pile.map(|_| self.fnd_count() + self.col_count() + self.temp_count())
     }
 
+    // return the ID of the reserve
+    pub fn first_reserve(&self) -> Option<usize> {
+        self.conf.reserve.map(|_| self.fnd_count() + self.col_count() + self.temp_count() + self.pile_count())
+    }
+
     // return the ID of the first free-cell pile
     pub fn first_temp(&self) -> Option<usize> {
         self.conf.temp.map(|_| self.fnd_count() + self.col_count())
@@ -384,23 +1082,41 @@ impl<'a> Game<'a> {
         }
     }
 
+    // returns true if the cursor must jump over a pile: removed and covered cards of a pyramid
+    // or a grid cannot be selected
+    fn is_hidden(&self, pile_id: usize) -> bool {
+        self.conf.layout != Layout::Columns
+            && self.is_col(pile_id)
+            && (self.piles[pile_id].cards.is_empty() || self.is_covered(pile_id))
+    }
+
     // move cursor to the defined direction
     pub fn move_selection(&mut self, dir: Direction) -> bool {
         match dir {
             Direction::Right => {
-                let col = self.selected.col;
-                if col == self.piles.len() - 1 {
-                    self.selected = Pos { col: 0, row: 0 };
-                } else {
-                    self.selected = Pos { col: col + 1, row: 0 };
+                for _ in 0..self.piles.len() {
+                    let col = self.selected.col;
+                    if col == self.piles.len() - 1 {
+                        self.selected = Pos { col: 0, row: 0 };
+                    } else {
+                        self.selected = Pos { col: col + 1, row: 0 };
+                    }
+                    if !self.is_hidden(self.selected.col) {
+                        break;
+                    }
                 }
             }
             Direction::Left => {
-                let col = self.selected.col;
-                if col == 0 {
-                    self.selected = Pos { col: self.piles.len() - 1, row: 0 };
-                } else {
-                    self.selected = Pos { col: col - 1, row: 0 };
+                for _ in 0..self.piles.len() {
+                    let col = self.selected.col;
+                    if col == 0 {
+                        self.selected = Pos { col: self.piles.len() - 1, row: 0 };
+                    } else {
+                        self.selected = Pos { col: col - 1, row: 0 };
+                    }
+                    if !self.is_hidden(self.selected.col) {
+                        break;
+                    }
                 }
             }
             Direction::Down => {
@@ -509,6 +1225,10 @@ impl<'a> Game<'a> {
                 let first = self.first_pile().unwrap();
                 self.move_next_pile_of_type(first, count);
             }
+            Direction::Reserve => match self.first_reserve() {
+                None => return false,
+                Some(first) => self.selected = Pos { col: first, row: 0 },
+            },
         }
         true
     }
@@ -519,11 +1239,11 @@ impl<'a> Game<'a> {
         let first_fnd = self.first_fnd().unwrap();
         let fnd_len = self.fnd_count();
         for (idx, pile) in self.piles.iter().enumerate() {
-            if idx >= first_fnd && idx < first_fnd + fnd_len {
+            if idx >= first_fnd && idx < first_fnd + fnd_len && !self.can_take_back(idx) {
                 continue;
             }
             let l = pile.cards.len();
-            if l == 0 {
+            if l == 0 || self.is_covered(idx) {
                 continue;
             }
             let ordered = self.ordered_count(idx);
@@ -531,7 +1251,9 @@ impl<'a> Game<'a> {
                 if (pile.conf.take_only || pile.conf.playable == Playable::Top) && cidx < l - 1 {
                     continue;
                 }
-                if pile.conf.playable == Playable::Ordered && l - cidx > ordered {
+                let in_order =
+                    matches!(pile.conf.playable, Playable::Ordered | Playable::SameSuit | Playable::Supermove);
+                if in_order && l - cidx > ordered {
                     continue;
                 }
                 if !card.is_up() {
@@ -566,56 +1288,152 @@ impl<'a> Game<'a> {
         dests
     }
 
-    // rollback the game to the previous snapshot if exists. The snapshot is deleted, so redo is
-    // unavailable.
+    // roll back the last move if exists. The move is saved to the redo list, so it can be
+    // replayed with redo.
     pub fn undo(&mut self) {
-        let mut last = match self.undo.pop() {
+        let mut last = match self.undo.pop_back() {
             None => return,
             Some(u) => u,
         };
-        self.redeals = last.redeals;
-        self.selected = last.selected;
-        for (idx, pile) in last.piles.drain(..).enumerate() {
-            self.piles[idx].cards = pile;
+        for change in last.changes.iter().rev() {
+            self.revert_change(*change);
         }
+        self.swap_state(&mut last);
+        self.redo.push_back(last);
     }
 
-    // empty the list of game snapshots, e.g. after winning the game
-    pub fn clear_undo(&mut self) {
-        self.undo.clear();
+    // replay the last move rolled back by undo if exists
+    pub fn redo(&mut self) {
+        let mut next = match self.redo.pop_back() {
+            None => return,
+            Some(u) => u,
+        };
+        for change in next.changes.iter() {
+            self.make_change(*change);
+        }
+        self.swap_state(&mut next);
+        self.undo.push_back(next);
     }
 
-    // the number of saved snapshots
+    // empty the list of moves to undo, e.g. after winning the game
+    pub fn clear_undo(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    // the number of moves that can be rolled back
     pub fn undo_count(&self) -> usize {
         self.undo.len()
     }
 
-    // create a game snapshot
-    pub fn take_snapshot(&mut self) {
-        let mut undo = Undo { redeals: self.redeals, piles: Vec::new(), selected: self.selected };
-        for pile in self.piles.iter() {
-            undo.piles.push(pile.cards.clone());
+    // returns true is any move can be replayed
+    pub fn has_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    // set the maximum number of moves that can be rolled back (0 - unlimited).
+    // The oldest moves are forgotten first
+    pub fn set_undo_limit(&mut self, limit: usize) {
+        self.undo_limit = limit;
+        self.trim_undo();
+    }
+
+    fn trim_undo(&mut self) {
+        while self.undo_limit != 0 && self.undo.len() > self.undo_limit {
+            self.undo.pop_front();
         }
-        self.undo.push(undo);
     }
 
-    // compare two last game snapshots. If they equal, the latest one is removed
-    pub fn squash_snapshots(&mut self) {
-        let l = self.undo.len();
-        if l < 2 {
-            return;
+    // exchange the game state with the one saved in the undo entry
+    fn swap_state(&mut self, u: &mut Undo) {
+        std::mem::swap(&mut self.redeals, &mut u.redeals);
+        std::mem::swap(&mut self.selected, &mut u.selected);
+        std::mem::swap(&mut self.log_len, &mut u.log_len);
+    }
+
+    fn make_change(&mut self, change: Change) {
+        match change {
+            Change::Move { from, to, count } => {
+                // a broken saved game must not crash the application
+                let l = self.piles[from].cards.len().saturating_sub(count);
+                let moved: CardList = self.piles[from].cards.drain(l..).collect();
+                self.piles[to].cards.extend(moved);
+            }
+            Change::Flip(pile) => {
+                if let Some(c) = self.piles[pile].cards.last_mut() {
+                    c.up = !c.up;
+                }
+            }
+            Change::Reverse(pile) => self.piles[pile].cards.reverse(),
+            Change::Shuffle { pile, seed } => {
+                let cards = &self.piles[pile].cards;
+                let shuffled = shuffled_order(seed, cards.len()).into_iter().map(|idx| cards[idx]).collect();
+                self.piles[pile].cards = shuffled;
+            }
         }
-        let last = &self.undo[l - 1];
-        let prev = &self.undo[l - 2];
-        if last.redeals != prev.redeals {
-            return;
+    }
+
+    fn revert_change(&mut self, change: Change) {
+        match change {
+            Change::Move { from, to, count } => self.make_change(Change::Move { from: to, to: from, count }),
+            Change::Flip(_) | Change::Reverse(_) => self.make_change(change),
+            Change::Shuffle { pile, seed } => {
+                let cards = &self.piles[pile].cards;
+                let mut restored = cards.clone();
+                for (idx, orig) in shuffled_order(seed, cards.len()).into_iter().enumerate() {
+                    restored[orig] = cards[idx];
+                }
+                self.piles[pile].cards = restored;
+            }
         }
-        for (idx, pile) in last.piles.iter().enumerate() {
-            if pile.len() != prev.piles[idx].len() {
-                return;
+    }
+
+    // add a change to the last undo entry, if any. The history is trimmed only when the entry
+    // gets its first change, so an entry removed by `squash_snapshots` does not push out an old one
+    fn track(&mut self, change: Change) {
+        let first = match self.undo.back_mut() {
+            None => return,
+            Some(u) => {
+                u.changes.push(change);
+                u.changes.len() == 1
+            }
+        };
+        if first {
+            self.trim_undo();
+        }
+    }
+
+    // move `count` top cards to another pile keeping their order
+    fn shift_cards(&mut self, from: usize, to: usize, count: usize) {
+        let change = Change::Move { from, to, count };
+        self.make_change(change);
+        self.track(change);
+    }
+
+    // turn over the top card of a pile if it is not face `up` yet
+    fn turn_top(&mut self, pile: usize, up: bool) {
+        match self.piles[pile].cards.last() {
+            Some(c) if c.up != up => {}
+            _ => return,
+        }
+        let change = Change::Flip(pile);
+        self.make_change(change);
+        self.track(change);
+    }
+
+    // start a new undo entry. All changes made after that are rolled back together
+    pub fn take_snapshot(&mut self) {
+        let undo = Undo { redeals: self.redeals, selected: self.selected, log_len: self.log_len, changes: Vec::new() };
+        self.undo.push_back(undo);
+    }
+
+    // remove the last undo entry if nothing has changed since it was taken
+    pub fn squash_snapshots(&mut self) {
+        if let Some(last) = self.undo.back() {
+            if last.changes.is_empty() && last.redeals == self.redeals && last.log_len == self.log_len {
+                self.undo.pop_back();
             }
         }
-        self.undo.pop();
     }
 
     // move a card(or a few ones if the card is not at the top of its pile), if possible.
@@ -656,31 +1474,34 @@ impl<'a> Game<'a> {
         }
 
         // all checks are done, moving the card
-        let cnt = from.row + 1;
-        let flippable = self.piles[from.col].conf.flip;
-        let cfrom = &mut self.piles[from.col].cards;
-        let l = cfrom.len();
-
-        let mut to_move: CardList = Vec::new();
-        for item in cfrom.iter().skip(l - cnt) {
-            to_move.push(*item);
-        }
-        cfrom.truncate(l - cnt);
-        let l = cfrom.len();
-        if l != 0 {
-            let crd = cfrom[l - 1];
-            if !crd.is_up() && flippable {
-                let mut c = cfrom.pop().unwrap();
-                c.up = true;
-                cfrom.push(c);
+        self.redo.clear();
+        if self.is_pairing() && !self.is_fnd(to.col) {
+            self.record(Move::Pair { a: from.col, b: to.col });
+            self.discard(from.col);
+            self.discard(to.col);
+        } else {
+            let cnt = from.row + 1;
+            self.record(Move::Card { from: from.col, to: to.col, count: cnt });
+            self.shift_cards(from.col, to.col, cnt);
+            if self.piles[from.col].conf.flip {
+                self.turn_top(from.col, true);
             }
         }
-        for c in to_move.drain(..) {
-            self.piles[to.col].cards.push(c);
-        }
+        self.collect_runs();
+        self.refill_from_reserve();
+        self.reveal_uncovered();
         Ok(())
     }
 
+    // move the top card of a pile to the first foundation that keeps all removed cards
+    fn discard(&mut self, pile_id: usize) {
+        let fnd = self.first_fnd().unwrap();
+        self.shift_cards(pile_id, fnd, 1);
+        if self.piles[pile_id].conf.flip {
+            self.turn_top(pile_id, true);
+        }
+    }
+
     // return a card at a given position or empty card if position is invalid.
     pub fn card_at(&self, loc: Pos) -> Card {
         if loc.is_empty() || loc.col >= self.piles.len() {
@@ -697,10 +1518,14 @@ impl<'a> Game<'a> {
     pub fn is_completed(&self) -> bool {
         let wl = self.first_fnd().unwrap();
         let wc = self.fnd_count();
+        let deck = self.first_pile();
         for (i, p) in self.piles.iter().enumerate() {
             if i >= wl && i < wl + wc {
                 continue;
             }
+            if self.conf.goal == Goal::Tableau && deck.is_some_and(|d| i >= d && i < d + self.pile_count()) {
+                continue;
+            }
             if !p.cards.is_empty() {
                 return false;
             }
@@ -731,10 +1556,19 @@ impl<'a> Game<'a> {
         self.conf.cols.len()
     }
 
+    // return the number of reserve piles: 0 or 1
+    pub fn reserve_count(&self) -> usize {
+        if self.conf.reserve.is_some() {
+            1
+        } else {
+            0
+        }
+    }
+
     // return the number of deck+waste
     pub fn pile_count(&self) -> usize {
         if let Some(ref pconf) = self.conf.pile {
-            if pconf.pile_to_cols {
+            if pconf.pile_to_cols || pconf.pile_to_fnd {
                 1
             } else {
                 2
@@ -787,6 +1621,14 @@ impl<'a> Game<'a> {
         Ok(&self.piles[tp].cards)
     }
 
+    // return cards in the reserve
+    pub fn reserve(&self) -> Result<&CardList, SolError> {
+        match self.first_reserve() {
+            None => Err(SolError::InvalidLocation),
+            Some(idx) => Ok(&self.piles[idx].cards),
+        }
+    }
+
     // return cards in a given column
     pub fn col(&self, pos: usize) -> Result<&CardList, SolError> {
         if pos >= self.col_count() {
@@ -836,19 +1678,28 @@ impl<'a> Game<'a> {
         }
         for i in 0..self.col_count() {
             let mut conf = SlotConf::new_for_col();
-            conf.playable = self.conf.playable;
-            conf.face_order = self.conf.col_forder;
-            conf.suit_order = self.conf.col_sorder;
-            conf.refill = self.conf.col_refill != Face::Empty && self.conf.col_refill != Face::Unavail;
-            conf.start_face = self.conf.col_refill;
+            let rules = &self.conf.cols[i].rules;
+            let refill = rules.refill.unwrap_or(self.conf.col_refill);
+            conf.playable = rules.playable.unwrap_or(self.conf.playable);
+            conf.face_order = rules.forder.unwrap_or(self.conf.col_forder);
+            conf.suit_order = rules.sorder.unwrap_or(self.conf.col_sorder);
+            conf.refill = refill != Face::Empty && refill != Face::Unavail;
+            conf.start_face = refill;
             conf.take_only = conf.take_only || self.conf.cols[i].take_only;
             let p = Pile { conf, cards: Vec::new() };
             self.piles.push(p);
         }
-        for _i in 0..self.temp_count() {
+        for idx in 0..self.temp_count() {
             let mut conf = SlotConf::new_for_temp();
-            conf.take_only = false;
-            conf.refill = true;
+            if let Some(tc) = self.conf.temp {
+                conf.limit = usize::from(tc.limit);
+                if let Some((forder, sorder)) = tc.order {
+                    conf.face_order = forder;
+                    conf.suit_order = sorder;
+                }
+                conf.refill = tc.is_refillable(idx);
+                conf.take_only = !conf.refill;
+            }
             let p = Pile { conf, cards: Vec::new() };
             self.piles.push(p);
         }
@@ -859,6 +1710,10 @@ impl<'a> Game<'a> {
             let p = Pile { conf, cards: Vec::new() };
             self.piles.push(p);
         }
+        if self.conf.reserve.is_some() {
+            let p = Pile { conf: SlotConf::new_for_reserve(), cards: Vec::new() };
+            self.piles.push(p);
+        }
     }
 
     fn update_fnd(&mut self, rand_card: &Card) {
@@ -881,9 +1736,11 @@ impl<'a> Game<'a> {
         let idx = self.first_col().unwrap();
         let ccols = self.conf.cols.clone();
         let mut first_col_card = Card::new_empty();
+        // in a free layout, covered columns may start face-down: they are turned when uncovered
+        let free = matches!(self.conf.layout, Layout::Free(_));
         for (n, cfg) in ccols.iter().enumerate() {
             let cnt = cfg.count;
-            let up = if cfg.up == 0 && cnt != 0 {
+            let up = if cfg.up == 0 && cnt != 0 && !free {
                 1
             } else if cfg.up > cfg.count {
                 cfg.count
@@ -903,10 +1760,15 @@ impl<'a> Game<'a> {
             let idx = self.first_temp().unwrap();
             let tcnt = usize::from(tc.count);
             for i in 0..tcnt {
-                self.piles[idx + i].cards = Vec::new();
+                self.piles[idx + i].cards = self.gen_list(usize::from(tc.deal), usize::from(tc.deal))?;
             }
         }
 
+        if let Some(rc) = self.conf.reserve {
+            let idx = self.first_reserve().unwrap();
+            self.piles[idx].cards = self.gen_list(usize::from(rc.count), usize::from(rc.up))?;
+        }
+
         if self.conf.pile.is_none() && !self.deck.is_empty() {
             return Err(SolError::UnusedCards);
         }
@@ -915,14 +1777,16 @@ impl<'a> Game<'a> {
             while let Some(crd) = self.deck.deal_card() {
                 self.piles[idx].cards.push(crd);
             }
+            let dest = if pconf.pile_to_fnd { self.first_fnd().unwrap() } else { idx + 1 };
             let mut cnt = pconf.deal_by;
             while !self.piles[idx].cards.is_empty() && !pconf.pile_to_cols && cnt != 0 {
                 let mut crd = self.piles[idx].cards.pop().unwrap();
                 crd.up = true;
-                self.piles[idx + 1].cards.push(crd);
+                self.piles[dest].cards.push(crd);
                 cnt -= 1;
             }
         }
+        self.reveal_uncovered();
         Ok(())
     }
 
@@ -953,8 +1817,11 @@ impl<'a> Game<'a> {
             return false;
         }
         let idx = self.first_pile().unwrap();
+        if self.deals_to_grid() {
+            return self.has_gaps();
+        }
         if self.pile_count() == 1 {
-            return !self.piles[idx].cards.is_empty();
+            return (!self.piles[idx].cards.is_empty() && !self.deal_blocked()) || self.can_redeal();
         }
         !self.piles[idx].cards.is_empty() || self.redeals != 0
     }
@@ -966,49 +1833,1087 @@ impl<'a> Game<'a> {
             return false;
         }
         let idx = self.first_pile().unwrap();
+        if self.deals_to_grid() {
+            if !self.has_gaps() {
+                return false;
+            }
+            self.redo.clear();
+            self.record(Move::Deal);
+            self.compact_grid();
+            return true;
+        }
         if self.piles[idx].cards.is_empty() && self.redeals == 0 {
             return false;
         }
 
-        if let Some(ref pconf) = self.conf.pile {
+        if let Some(pconf) = self.conf.pile {
+            // without waste, the empty deck is refilled from columns
+            if self.pile_count() == 1 && self.piles[idx].cards.is_empty() {
+                if !self.can_redeal() {
+                    return false;
+                }
+                self.redo.clear();
+                return self.redeal();
+            }
+
+            // deal to the foundation
+            if pconf.pile_to_fnd {
+                if self.piles[idx].cards.is_empty() {
+                    return false;
+                }
+                self.redo.clear();
+                self.record(Move::Deal);
+                let fnd = self.first_fnd().unwrap();
+                let mut cnt = self.deal_by();
+                while !self.piles[idx].cards.is_empty() && cnt != 0 {
+                    self.shift_cards(idx, fnd, 1);
+                    self.turn_top(fnd, true);
+                    cnt -= 1;
+                }
+                return true;
+            }
+
             // deal to columns
             if pconf.pile_to_cols {
-                let col_first = self.first_col().unwrap();
-                for col_idx in 0..self.col_count() {
-                    if self.piles[col_idx + col_first].conf.take_only {
-                        continue;
-                    }
-                    if self.piles[idx].cards.is_empty() {
-                        break;
+                if self.deal_blocked() {
+                    return false;
+                }
+                if !self.piles[idx].cards.is_empty() {
+                    self.redo.clear();
+                    self.record(Move::Deal);
+                }
+                for (col, count) in self.deal_targets() {
+                    for _ in 0..count {
+                        if self.piles[idx].cards.is_empty() {
+                            break;
+                        }
+                        self.shift_cards(idx, col, 1);
+                        self.turn_top(col, true);
                     }
-                    let mut crd = self.piles[idx].cards.pop().unwrap();
-                    crd.up = true;
-                    self.piles[col_idx + col_first].cards.push(crd);
                 }
+                self.collect_runs();
                 return true;
             }
         }
 
         // if the deck is empty, move cards from the "waste" to the "deck" at first
-        if self.piles[idx].cards.is_empty() && !self.piles[idx + 1].cards.is_empty() {
-            self.redeals -= 1;
-            while !self.piles[idx + 1].cards.is_empty() {
-                let mut card = self.piles[idx + 1].cards.pop().unwrap();
-                card.up = false;
-                self.piles[idx].cards.push(card);
-            }
-        }
+        self.redeal();
         if self.piles[idx].cards.is_empty() {
             return false;
         }
+        self.redo.clear();
+        self.record(Move::Deal);
         // put a few top cards to the "waste"
-        let mut cnt = self.conf.deal_by();
+        let mut cnt = self.deal_by();
         while !self.piles[idx].cards.is_empty() && cnt != 0 {
-            let mut crd = self.piles[idx].cards.pop().unwrap();
-            crd.up = true;
-            self.piles[idx + 1].cards.push(crd);
+            self.shift_cards(idx, idx + 1, 1);
+            self.turn_top(idx + 1, true);
             cnt -= 1;
         }
         true
     }
+
+    // the number of cards in a full K-to-A run of the same suit: every rank of the deck
+    fn full_run(&self) -> usize {
+        self.conf.deck.faces.len()
+    }
+
+    // returns true if the top cards of a pile make a full K-to-A run of the same suit. With a high
+    // ace the run goes from A to 2
+    fn has_full_run(&self, pile_id: usize) -> bool {
+        let cards = &self.piles[pile_id].cards;
+        let (l, run_len, faces, order) = (cards.len(), self.full_run(), &self.conf.deck.faces, self.conf.rank_order);
+        let highest =
+            if order.ace == Ace::High && faces.first() == Some(&Face::A) { faces.first() } else { faces.last() };
+        if l < run_len || Some(&cards[l - run_len].face) != highest {
+            return false;
+        }
+        let run = &cards[l - run_len..];
+        run.iter().all(|c| c.is_up())
+            && run.windows(2).all(|w| w[0].is_same_suit(&w[1]) && w[1].diff_in(&w[0], faces, order) == 1)
+    }
+
+    // move all full runs from columns to empty foundations if the rules say so
+    fn collect_runs(&mut self) {
+        if self.conf.collect != Collect::Sequence {
+            return;
+        }
+        let first_col = self.first_col().unwrap();
+        let first_fnd = self.first_fnd().unwrap();
+        for col in first_col..first_col + self.col_count() {
+            while self.has_full_run(col) {
+                let fnd = (first_fnd..first_fnd + self.fnd_count()).find(|&f| self.piles[f].cards.is_empty());
+                let fnd = match fnd {
+                    None => return,
+                    Some(f) => f,
+                };
+                self.shift_cards(col, fnd, self.full_run());
+                if self.piles[col].conf.flip {
+                    self.turn_top(col, true);
+                }
+            }
+        }
+    }
+
+    // returns true if any column that accepts cards is empty
+    fn has_empty_col(&self) -> bool {
+        let first = self.first_col().unwrap();
+        let cols = &self.piles[first..first + self.col_count()];
+        cols.iter().any(|p| !p.conf.take_only && p.conf.refill && p.cards.is_empty())
+    }
+
+    // columns that get cards when the deck deals to columns, and how many cards each of them gets
+    fn deal_targets(&self) -> Vec<(usize, usize)> {
+        let first = self.first_col().unwrap();
+        let deal_cols = self.conf.pile.map_or(DealCols::All, |p| p.deal_cols);
+        if let DealCols::Single(n) = deal_cols {
+            return vec![(first + usize::from(n), usize::from(self.deal_by()))];
+        }
+        (0..self.col_count())
+            .filter(|&idx| {
+                let pile = &self.piles[first + idx];
+                !pile.conf.take_only
+                    && match deal_cols {
+                        DealCols::Empty => pile.cards.is_empty(),
+                        DealCols::Subset(mask) => idx < 32 && mask & (1 << idx) != 0,
+                        _ => true,
+                    }
+            })
+            .map(|idx| (first + idx, 1))
+            .collect()
+    }
+
+    // returns the reason why the rules forbid dealing to columns right now, if any
+    pub fn deal_block(&self) -> Option<DealBlock> {
+        match self.conf.pile {
+            Some(ref pconf) if pconf.pile_to_cols => {
+                if pconf.require_filled && self.has_empty_col() {
+                    Some(DealBlock::EmptyCol)
+                } else if self.deal_targets().is_empty() {
+                    Some(DealBlock::NoTarget)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // returns true if the rules forbid dealing to columns right now, see `deal_block`
+    pub fn deal_blocked(&self) -> bool {
+        self.deal_block().is_some()
+    }
+
+    // returns true if the deck deals cards to the first foundation
+    fn deals_to_fnd(&self) -> bool {
+        match self.conf.pile {
+            Some(ref pconf) => pconf.pile_to_fnd,
+            None => false,
+        }
+    }
+
+    // returns true if the deck deals cards to the gaps of a grid
+    fn deals_to_grid(&self) -> bool {
+        match self.conf.pile {
+            Some(ref pconf) => pconf.pile_to_cols && matches!(self.conf.layout, Layout::Grid(_)),
+            None => false,
+        }
+    }
+
+    // returns true if any gap of a grid can be filled: an empty cell is followed by a non-empty
+    // one, or the deck has cards to put to the empty cell
+    fn has_gaps(&self) -> bool {
+        let first = self.first_col().unwrap();
+        let cols = &self.piles[first..first + self.col_count()];
+        let deck_empty = match self.first_pile() {
+            Some(idx) => self.piles[idx].cards.is_empty(),
+            None => true,
+        };
+        match cols.iter().position(|p| p.cards.is_empty()) {
+            None => false,
+            Some(gap) => !deck_empty || cols[gap..].iter().any(|p| !p.cards.is_empty()),
+        }
+    }
+
+    // move all cards of a grid to its beginning keeping their order, and then fill the empty
+    // cells at the end with cards from the deck
+    fn compact_grid(&mut self) {
+        let first = self.first_col().unwrap();
+        let last = first + self.col_count();
+        let mut dest = first;
+        for col in first..last {
+            let cnt = self.piles[col].cards.len();
+            if cnt == 0 {
+                continue;
+            }
+            if col != dest {
+                self.shift_cards(col, dest, cnt);
+            }
+            dest += 1;
+        }
+        let deck = self.first_pile().unwrap();
+        for col in dest..last {
+            if self.piles[deck].cards.is_empty() {
+                break;
+            }
+            self.shift_cards(deck, col, 1);
+            self.turn_top(col, true);
+        }
+    }
+
+    // put the top reserve card to every empty column if the rules say so
+    fn refill_from_reserve(&mut self) {
+        let res = match self.conf.reserve {
+            Some(rc) if rc.refill_cols => self.first_reserve().unwrap(),
+            _ => return,
+        };
+        let first = self.first_col().unwrap();
+        for col in first..first + self.col_count() {
+            if self.piles[res].cards.is_empty() {
+                return;
+            }
+            if self.piles[col].conf.take_only || !self.piles[col].cards.is_empty() {
+                continue;
+            }
+            self.shift_cards(res, col, 1);
+            self.turn_top(col, true);
+            self.turn_top(res, true);
+        }
+    }
+
+    // move all cards from "waste" back to "deck" if the deck is empty and a redeal is left. With
+    // `RedealMode::Shuffle` the cards of columns are gathered as well and dealt again after shuffling
+    fn redeal(&mut self) -> bool {
+        if !self.can_redeal() {
+            return false;
+        }
+        let idx = self.first_pile().unwrap();
+        let mode = self.conf.pile.map_or(RedealMode::Turn, |p| p.redeal);
+        // every pass gets its own shuffle
+        let pass = (i64::from(self.conf.redeals()) - i64::from(self.redeals)) as u64;
+        self.redeals -= 1;
+        self.record(Move::Redeal);
+        let first_col = self.first_col().unwrap();
+        let mut sources: Vec<usize> = Vec::new();
+        if mode == RedealMode::Shuffle {
+            sources.extend(first_col..first_col + self.col_count());
+        }
+        if self.pile_count() == 2 {
+            sources.push(idx + 1);
+        }
+        for src in sources {
+            while !self.piles[src].cards.is_empty() {
+                self.shift_cards(src, idx, 1);
+                self.turn_top(idx, false);
+            }
+        }
+        match mode {
+            RedealMode::Turn => {}
+            RedealMode::Keep => {
+                let change = Change::Reverse(idx);
+                self.make_change(change);
+                self.track(change);
+            }
+            RedealMode::Shuffle => {
+                let change = Change::Shuffle { pile: idx, seed: self.seed ^ pass.wrapping_mul(0x9e37_79b9_7f4a_7c15) };
+                self.make_change(change);
+                self.track(change);
+                for (col_idx, cc) in self.conf.cols.clone().iter().enumerate() {
+                    let left = self.piles[idx].cards.len();
+                    let (count, up) = (left.min(usize::from(cc.count)), usize::from(cc.up));
+                    for n in 0..count {
+                        self.shift_cards(idx, first_col + col_idx, 1);
+                        if n + up >= count {
+                            self.turn_top(first_col + col_idx, true);
+                        }
+                    }
+                }
+                self.reveal_uncovered();
+                self.collect_runs();
+            }
+        }
+        true
+    }
+
+    // the number of cards to deal at a time: it may differ for the first pass and the next ones
+    fn deal_by(&self) -> u8 {
+        match self.conf.pile {
+            Some(pconf) if pconf.redeal_by != 0 && self.redeals != self.conf.redeals() => pconf.redeal_by,
+            _ => self.conf.deal_by(),
+        }
+    }
+
+    // add a move to the log. The moves rolled back by undo are dropped
+    fn record(&mut self, mv: Move) {
+        self.log.truncate(self.log_len);
+        self.log.push(LogEntry { time: self.clock, mv });
+        self.log_len += 1;
+    }
+
+    // set the current game time. All moves made after that are logged with this time
+    pub fn set_clock(&mut self, time: Duration) {
+        self.clock = time;
+    }
+
+    // return all moves made since the game start. Moves rolled back by undo are excluded
+    pub fn log(&self) -> &[LogEntry] {
+        &self.log[..self.log_len]
+    }
+
+    // the name of the solitaire
+    pub fn rules_name(&self) -> &str {
+        &self.conf.name
+    }
+
+    // how columns are placed in play area
+    pub fn layout(&self) -> &Layout {
+        &self.conf.layout
+    }
+
+    // game score: every card in foundations brings points, and every card taken back from
+    // foundations costs the penalty set by the rules. Moves rolled back by undo do not count
+    pub fn score(&self) -> i32 {
+        let first = self.first_fnd().unwrap();
+        let in_fnd: usize = (first..first + self.fnd_count()).map(|idx| self.piles[idx].cards.len()).sum();
+        let taken_back = self
+            .log()
+            .iter()
+            .filter(|e| matches!(e.mv, Move::Card { from, to, .. } if self.is_fnd(from) && !self.is_fnd(to)))
+            .count();
+        in_fnd as i32 * FND_CARD_SCORE - taken_back as i32 * i32::from(self.conf.fnd_penalty)
+    }
+
+    // whether K and A are neighbors in sequences and whether A is high or low
+    pub fn rank_order(&self) -> RankOrder {
+        self.conf.rank_order
+    }
+
+    fn can_redeal(&self) -> bool {
+        let shuffle = matches!(self.conf.pile, Some(pconf) if pconf.redeal == RedealMode::Shuffle);
+        if self.pile_count() == 0 || (self.pile_count() != 2 && !shuffle) || self.redeals == 0 {
+            return false;
+        }
+        let idx = self.first_pile().unwrap();
+        if !self.piles[idx].cards.is_empty() {
+            return false;
+        }
+        if self.pile_count() == 2 && !self.piles[idx + 1].cards.is_empty() {
+            return true;
+        }
+        let first_col = self.first_col().unwrap();
+        shuffle && (first_col..first_col + self.col_count()).any(|col| !self.piles[col].cards.is_empty())
+    }
+
+    // return all moves that can be done in the current position: card moves in the order of
+    // avail_list, then dealing or redealing cards if possible
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        for from in self.avail_list() {
+            for to in self.dest_list_card(from) {
+                if self.is_pairing() && !self.is_fnd(to.col) {
+                    // a pair is the same whichever card is taken first
+                    if from.col < to.col {
+                        moves.push(Move::Pair { a: from.col, b: to.col });
+                    }
+                    continue;
+                }
+                moves.push(Move::Card { from: from.col, to: to.col, count: from.row + 1 });
+            }
+        }
+        if let Some(idx) = self.first_pile() {
+            if self.deals_to_grid() {
+                if self.has_gaps() {
+                    moves.push(Move::Deal);
+                }
+            } else if !self.piles[idx].cards.is_empty() {
+                if !self.deal_blocked() {
+                    moves.push(Move::Deal);
+                }
+            } else if self.can_redeal() {
+                moves.push(Move::Redeal);
+            }
+        }
+        moves
+    }
+
+    // make a move without saving the current state to undo list
+    pub(crate) fn exec(&mut self, mv: Move) -> Result<(), SolError> {
+        match mv {
+            Move::Card { from, to, count } => {
+                // in pairing games cards are removed by pairs, and only a single card goes to a foundation
+                if count == 0 || from == to || (self.is_pairing() && !self.is_fnd(to)) {
+                    return Err(SolError::InvalidMove);
+                }
+                // the destination checks only the bottom card of the run, so the run itself must
+                // be the one a player can take: face-up and in order if the rules require it
+                let pos = Pos { col: from, row: count - 1 };
+                if !self.is_selectable(Some(pos)) {
+                    return Err(SolError::Unplayable);
+                }
+                self.move_card(pos, Pos { col: to, row: 0 })
+            }
+            Move::Pair { a, b } => {
+                if !self.is_pairing() || self.is_fnd(b) {
+                    return Err(SolError::InvalidMove);
+                }
+                self.move_card(Pos { col: a, row: 0 }, Pos { col: b, row: 0 })
+            }
+            Move::Deal => {
+                let idx = match self.first_pile() {
+                    Some(idx) => idx,
+                    None => return Err(SolError::InvalidMove),
+                };
+                if (self.piles[idx].cards.is_empty() && !self.deals_to_grid()) || !self.deal() {
+                    return Err(SolError::InvalidMove);
+                }
+                Ok(())
+            }
+            Move::Redeal => {
+                if !self.redeal() {
+                    return Err(SolError::InvalidMove);
+                }
+                self.redo.clear();
+                Ok(())
+            }
+        }
+    }
+
+    // make a move. The game state before the move is saved to undo list
+    pub fn apply(&mut self, mv: Move) -> Result<(), SolError> {
+        self.take_snapshot();
+        let res = self.exec(mv);
+        if res.is_err() {
+            self.undo.pop_back();
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod engine_test {
+    use super::*;
+    use crate::gconf::{klondike_conf, ColConf};
+
+    fn is_send_clone<T: Send + Sync + Clone>() {}
+
+    #[test]
+    fn game_is_send() {
+        is_send_clone::<Game>();
+    }
+
+    // `n` columns with the same number of cards and face-up cards
+    fn cols(count: u8, up: u8, n: usize) -> Vec<ColConf> {
+        vec![ColConf::new(count, up); n]
+    }
+
+    fn up_card(suit: Suit, face: Face) -> Card {
+        Card { up: true, ..Card::new(suit, face) }
+    }
+
+    fn layout(game: &Game) -> Vec<String> {
+        game.piles.iter().map(|p| cards_to_str(&p.cards)).collect()
+    }
+
+    #[test]
+    fn undo_redo_exact() {
+        let mut conf = klondike_conf();
+        conf.pile.as_mut().unwrap().deal_by = 3;
+        let mut game = Game::init(conf, Some(5)).unwrap();
+        let mut states = vec![layout(&game)];
+        for step in 0..60 {
+            let moves = game.legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+            game.apply(moves[step % moves.len()]).unwrap();
+            states.push(layout(&game));
+        }
+        let last = states.pop().unwrap();
+        while let Some(state) = states.pop() {
+            game.undo();
+            assert_eq!(layout(&game), state);
+        }
+        assert_eq!(game.undo_count(), 0);
+        while game.has_redo() {
+            game.redo();
+        }
+        assert_eq!(layout(&game), last);
+
+        game.set_undo_limit(5);
+        assert_eq!(game.undo_count(), 5);
+        game.take_snapshot();
+        game.squash_snapshots();
+        assert_eq!(game.undo_count(), 5);
+    }
+
+    #[test]
+    fn redo_after_undo() {
+        let mut game = Game::init(klondike_conf(), Some(5)).unwrap();
+        let start = layout(&game);
+        game.redo();
+        assert_eq!(layout(&game), start);
+        game.take_snapshot();
+        assert!(game.deal());
+        let dealt = layout(&game);
+        game.undo();
+        assert_eq!(layout(&game), start);
+        assert!(game.has_redo());
+        game.redo();
+        assert_eq!(layout(&game), dealt);
+        assert!(!game.has_redo());
+        // a new move drops the moves rolled back by undo
+        game.undo();
+        game.take_snapshot();
+        assert!(game.deal());
+        assert!(!game.has_redo());
+        assert_eq!(game.undo_count(), 1);
+    }
+
+    #[test]
+    fn autoplay_stops_at_unsafe_card() {
+        let mut conf = klondike_conf();
+        conf.cols = cols(1, 1, 4);
+        let mut game = Game::init(conf, Some(5)).unwrap();
+        for pile in game.piles.iter_mut() {
+            pile.cards.clear();
+        }
+        let (col, fnd) = (game.first_col().unwrap(), game.first_fnd().unwrap());
+        game.piles[fnd].cards = vec![up_card(Suit::Spade, Face::A)];
+        game.piles[col].cards = vec![up_card(Suit::Spade, Face::N2)];
+        game.piles[col + 1].cards = vec![up_card(Suit::Heart, Face::A)];
+        game.piles[col + 2].cards = vec![up_card(Suit::Spade, Face::N4), up_card(Suit::Spade, Face::N3)];
+        game.piles[col + 3].cards = vec![Card::new(Suit::Heart, Face::N3), up_card(Suit::Club, Face::K)];
+        // 2 of spades waits for the ace of hearts, and 4 of spades stays for the face-down 3 of hearts
+        assert_eq!(game.autoplay(), 3);
+        assert_eq!(game.fnd(0).unwrap().len(), 3);
+        assert_eq!(game.fnd(1).unwrap().len(), 1);
+        assert_eq!(game.piles[col + 2].cards.len(), 1);
+        assert_eq!(game.undo_count(), 3);
+        assert_eq!(game.autoplay(), 0);
+    }
+
+    // foundations are built up to queens, and the kings are face-up in the first columns
+    fn near_won(game: &mut Game) {
+        const FACES: [Face; 12] = [
+            Face::A,
+            Face::N2,
+            Face::N3,
+            Face::N4,
+            Face::N5,
+            Face::N6,
+            Face::N7,
+            Face::N8,
+            Face::N9,
+            Face::N10,
+            Face::J,
+            Face::Q,
+        ];
+        for pile in game.piles.iter_mut() {
+            pile.cards.clear();
+        }
+        let (fnd, col) = (game.first_fnd().unwrap(), game.first_col().unwrap());
+        for (idx, suit) in [Suit::Spade, Suit::Club, Suit::Diamond, Suit::Heart].iter().enumerate() {
+            game.piles[fnd + idx].cards = FACES.iter().map(|f| up_card(*suit, *f)).collect();
+            game.piles[col + idx].cards = vec![up_card(*suit, Face::K)];
+        }
+    }
+
+    #[test]
+    fn autocomplete_conditions() {
+        let mut game = Game::init(klondike_conf(), Some(5)).unwrap();
+        assert!(!game.can_autocomplete());
+        let (col, deck) = (game.first_col().unwrap(), game.first_pile().unwrap());
+        near_won(&mut game);
+        game.piles[col].cards[0].up = false;
+        assert!(!game.can_autocomplete());
+        assert!(!game.autocomplete());
+        near_won(&mut game);
+        let king = game.piles[col].cards.pop().unwrap();
+        game.piles[deck].cards.push(king);
+        assert!(!game.can_autocomplete());
+        near_won(&mut game);
+        let king = game.piles[col].cards.pop().unwrap();
+        game.piles[deck + 1].cards.push(Card { up: true, ..king });
+        assert!(!game.can_autocomplete());
+
+        near_won(&mut game);
+        assert!(game.can_autocomplete());
+        assert!(game.autocomplete());
+        assert!(game.is_completed());
+        assert!(!game.can_autocomplete());
+    }
+
+    #[test]
+    fn dead_end() {
+        let mut conf = klondike_conf();
+        conf.pile.as_mut().unwrap().redeals = 0;
+        let mut game = Game::init(conf, Some(5)).unwrap();
+        assert!(!game.is_dead_end());
+        for pile in game.piles.iter_mut() {
+            pile.cards.clear();
+        }
+        let col = game.first_col().unwrap();
+        let cards = [
+            (Suit::Spade, Face::N5),
+            (Suit::Club, Face::N5),
+            (Suit::Diamond, Face::N5),
+            (Suit::Heart, Face::N5),
+            (Suit::Spade, Face::N9),
+            (Suit::Club, Face::N9),
+            (Suit::Diamond, Face::N9),
+        ];
+        for (idx, (suit, face)) in cards.iter().enumerate() {
+            game.piles[col + idx].cards = vec![up_card(*suit, *face)];
+        }
+        // no card fits another one, and the deck and waste are empty
+        assert!(game.is_dead_end());
+        game.piles[col + 6].cards = vec![up_card(Suit::Heart, Face::A)];
+        assert!(!game.is_dead_end());
+    }
+
+    #[test]
+    fn apply_checks_run() {
+        let mut conf = klondike_conf();
+        conf.playable = Playable::Ordered;
+        conf.cols = cols(1, 1, 4);
+        let mut game = Game::init(conf, Some(5)).unwrap();
+        let col = game.first_col().unwrap();
+        game.piles[col].cards = vec![
+            Card::new(Suit::Club, Face::N2),
+            up_card(Suit::Spade, Face::N9),
+            up_card(Suit::Heart, Face::N5),
+            up_card(Suit::Spade, Face::N4),
+        ];
+        game.piles[col + 1].cards = vec![up_card(Suit::Heart, Face::N10)];
+        game.piles[col + 2].cards = vec![up_card(Suit::Club, Face::N6)];
+        let before = layout(&game);
+        // 9 fits 10, but 9 and 5 are not in order
+        let unordered = Move::Card { from: col, to: col + 1, count: 3 };
+        assert!(!game.legal_moves().contains(&unordered));
+        assert!(game.apply(unordered).is_err());
+        // the bottom card of the run is face-down
+        assert!(game.apply(Move::Card { from: col, to: col + 1, count: 4 }).is_err());
+        assert!(game.apply(Move::Card { from: col, to: col, count: 1 }).is_err());
+        assert_eq!(layout(&game), before);
+        assert_eq!(game.undo_count(), 0);
+
+        let ordered = Move::Card { from: col, to: col + 2, count: 2 };
+        assert!(game.legal_moves().contains(&ordered));
+        game.apply(ordered).unwrap();
+        assert_eq!(game.piles[col + 2].cards.len(), 3);
+    }
+
+    #[test]
+    fn apply_matches_legal_moves() {
+        let mut game = Game::init(klondike_conf(), Some(11)).unwrap();
+        for step in 0..30 {
+            let legal = game.legal_moves();
+            if legal.is_empty() {
+                break;
+            }
+            // a card move is accepted only if it is in the list of legal ones
+            for from in 0..game.piles.len() {
+                for to in 0..game.piles.len() {
+                    for count in 1..=game.piles[from].cards.len() {
+                        let mv = Move::Card { from, to, count };
+                        assert_eq!(game.fork().apply(mv).is_ok(), legal.contains(&mv), "{:?}", mv);
+                    }
+                }
+            }
+            game.apply(legal[step % legal.len()]).unwrap();
+        }
+    }
+
+    #[test]
+    fn reserve_refills_columns() {
+        use crate::gconf::ReserveConf;
+        let mut conf = klondike_conf();
+        conf.name = "Canfield".to_string();
+        conf.pile.as_mut().unwrap().deal_by = 3;
+        conf.reserve = Some(ReserveConf { count: 13, up: 1, refill_cols: true });
+        conf.cols = cols(1, 1, 4);
+        conf.col_refill = Face::Any;
+        let conf = Arc::new(conf);
+        let mut tested = false;
+        for seed in 1..200 {
+            let mut game = Game::init(Arc::clone(&conf), Some(seed)).unwrap();
+            let res = game.first_reserve().unwrap();
+            assert_eq!(game.reserve().unwrap().len(), 13);
+            assert_eq!(game.piles[res].cards.iter().filter(|c| c.is_up()).count(), 1);
+            // a column with a single card gets empty after any move from it
+            let mv =
+                game.legal_moves().into_iter().find(|mv| matches!(mv, Move::Card { from, .. } if game.is_col(*from)));
+            let (mv, from) = match mv {
+                Some(mv @ Move::Card { from, .. }) => (mv, from),
+                _ => continue,
+            };
+            let before = layout(&game);
+            game.apply(mv).unwrap();
+            assert_eq!(game.piles[from].cards.len(), 1);
+            assert_eq!(game.reserve().unwrap().len(), 12);
+            assert!(game.piles[res].cards.last().unwrap().is_up());
+            game.undo();
+            assert_eq!(layout(&game), before);
+            tested = true;
+            break;
+        }
+        assert!(tested);
+    }
+
+    #[test]
+    fn full_run_collected() {
+        let mut conf = klondike_conf();
+        conf.name = "Spider".to_string();
+        conf.playable = Playable::SameSuit;
+        let pconf = conf.pile.as_mut().unwrap();
+        (pconf.redeals, pconf.pile_to_cols, pconf.require_filled) = (0, true, true);
+        for slot in conf.fnd.iter_mut() {
+            (slot.first, slot.forder) = (Face::K, FaceOrder::Desc);
+        }
+        conf.collect = Collect::Sequence;
+        conf.cols = cols(5, 1, 4);
+        (conf.col_sorder, conf.col_refill) = (SuitOrder::Any, Face::Any);
+        let mut game = Game::init(conf, Some(3)).unwrap();
+        let (col0, col1) = (game.first_col().unwrap(), game.first_col().unwrap() + 1);
+        assert!(game.legal_moves().contains(&Move::Deal));
+        let faces = [
+            Face::K,
+            Face::Q,
+            Face::J,
+            Face::N10,
+            Face::N9,
+            Face::N8,
+            Face::N7,
+            Face::N6,
+            Face::N5,
+            Face::N4,
+            Face::N3,
+            Face::N2,
+        ];
+        game.piles[col0].cards = faces.iter().map(|f| Card { up: true, ..Card::new(Suit::Spade, *f) }).collect();
+        game.piles[col1].cards = vec![Card { up: true, ..Card::new(Suit::Spade, Face::A) }];
+        assert_eq!(game.ordered_count(col0), 12);
+        // a single card never goes to a foundation
+        assert!(!game.dest_list_card(Pos { col: col1, row: 0 }).iter().any(|p| game.is_fnd(p.col)));
+
+        game.apply(Move::Card { from: col1, to: col0, count: 1 }).unwrap();
+        assert!(game.piles[col0].cards.is_empty());
+        assert_eq!(game.fnd(0).unwrap().len(), 13);
+        // both columns are empty now, so the deck cannot be dealt
+        assert!(!game.can_deal());
+        assert_eq!(game.deal_block(), Some(DealBlock::EmptyCol));
+        assert!(!game.legal_moves().contains(&Move::Deal));
+        game.undo();
+        assert_eq!(game.piles[col0].cards.len(), 12);
+        assert!(game.fnd(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn jokers_are_wild() {
+        let mut conf = klondike_conf();
+        conf.deck.jokers = 2;
+        conf.cols = cols(1, 1, 4);
+        assert!(conf.validate().is_ok());
+        let mut game = Game::init(conf.clone(), Some(5)).unwrap();
+        let (col, fnd) = (game.first_col().unwrap(), game.first_fnd().unwrap());
+        let joker = up_card(Suit::Spade, Face::Joker);
+        game.piles[col].cards = vec![up_card(Suit::Spade, Face::N9), joker, up_card(Suit::Club, Face::N7)];
+        game.piles[col + 1].cards = vec![up_card(Suit::Club, Face::N9)];
+        game.piles[col + 2].cards = vec![up_card(Suit::Diamond, Face::N9)];
+        game.piles[col + 3].cards.clear();
+        game.piles[fnd].cards = vec![up_card(Suit::Spade, Face::A), up_card(Suit::Spade, Face::N2)];
+        // the joker takes the place of a red 8
+        assert_eq!(game.ordered_count(col), 3);
+        assert!(game.can_move(Pos { col, row: 1 }, col + 1));
+        assert!(!game.can_move(Pos { col, row: 1 }, col + 2));
+        // a joker can start an empty column, and it is skipped in foundations
+        assert!(game.can_move(Pos { col, row: 1 }, col + 3));
+        game.piles[col].cards.pop();
+        assert!(game.can_move(Pos { col, row: 0 }, fnd));
+        game.apply(Move::Card { from: col, to: fnd, count: 1 }).unwrap();
+        game.piles[col].cards = vec![up_card(Suit::Spade, Face::N3)];
+        assert!(game.can_move(Pos { col, row: 0 }, fnd));
+
+        conf.collect = Collect::Sequence;
+        assert!(conf.validate().is_err());
+    }
+
+    #[test]
+    fn fnd_take_back() {
+        let mut conf = klondike_conf();
+        conf.fnd_penalty = 15;
+        conf.cols = cols(1, 1, 4);
+        for playable in [false, true] {
+            conf.fnd_playable = playable;
+            let mut game = Game::init(conf.clone(), Some(5)).unwrap();
+            let (col, fnd) = (game.first_col().unwrap(), game.first_fnd().unwrap());
+            game.piles[col].cards = vec![up_card(Suit::Heart, Face::N2)];
+            game.piles[fnd].cards = vec![up_card(Suit::Spade, Face::A)];
+            assert_eq!(game.score(), 10);
+            let from = Pos { col: fnd, row: 0 };
+            assert_eq!(game.can_move(from, col), playable);
+            assert_eq!(game.is_selectable(Some(from)), playable);
+            assert_eq!(game.avail_list().contains(&from), playable);
+            // a card never goes from one foundation to another
+            assert!(!game.can_move(from, fnd + 1));
+            if playable {
+                game.take_snapshot();
+                game.move_card(from, Pos { col, row: 0 }).unwrap();
+                assert_eq!(game.score(), -15);
+                game.undo();
+                assert_eq!(game.score(), 10);
+            }
+        }
+        conf.fnd_playable = true;
+        conf.collect = Collect::Sequence;
+        assert!(conf.validate().is_err());
+    }
+
+    #[test]
+    fn supermove_capacity() {
+        use crate::gconf::TempConf;
+        let mut conf = klondike_conf();
+        conf.name = "Free cell".to_string();
+        (conf.playable, conf.pile, conf.temp) = (Playable::Supermove, None, Some(TempConf::new(2)));
+        conf.cols = cols(13, 13, 4);
+        conf.col_refill = Face::Any;
+        let mut game = Game::init(conf, Some(5)).unwrap();
+        let (col, temp) = (game.first_col().unwrap(), game.first_temp().unwrap());
+        game.piles[col].cards = vec![
+            up_card(Suit::Spade, Face::N6),
+            up_card(Suit::Heart, Face::N5),
+            up_card(Suit::Spade, Face::N4),
+            up_card(Suit::Heart, Face::N3),
+        ];
+        game.piles[col + 1].cards = vec![up_card(Suit::Heart, Face::N7)];
+        game.piles[col + 2].cards.clear();
+        game.piles[col + 3].cards.clear();
+        game.piles[temp].cards.clear();
+        game.piles[temp + 1].cards.clear();
+        let run = Pos { col, row: 3 };
+        // (2 + 1) * 2^2
+        assert!(game.can_move(run, col + 1));
+
+        game.piles[col + 2].cards = vec![up_card(Suit::Club, Face::K)];
+        game.piles[temp].cards = vec![up_card(Suit::Club, Face::Q)];
+        game.piles[temp + 1].cards = vec![up_card(Suit::Club, Face::J)];
+        // (0 + 1) * 2^1
+        assert!(!game.can_move(run, col + 1));
+        assert!(!game.dest_list_card(run).contains(&Pos { col: col + 1, row: 0 }));
+        game.piles[temp + 1].cards.clear();
+        // (1 + 1) * 2^1
+        assert!(game.can_move(run, col + 1));
+        // the destination column does not count: (1 + 1) * 2^0
+        assert!(game.can_move(Pos { col, row: 1 }, col + 3));
+        assert!(!game.can_move(Pos { col, row: 2 }, col + 3));
+    }
+
+    #[test]
+    fn redeal_modes() {
+        let mut conf = klondike_conf();
+        let pconf = conf.pile.as_mut().unwrap();
+        (pconf.deal_by, pconf.redeal_by, pconf.redeals) = (3, 1, 1);
+        conf.cols = cols(10, 1, 4);
+        for (mode, first_out) in [(RedealMode::Turn, 0usize), (RedealMode::Keep, 11)] {
+            conf.pile.as_mut().unwrap().redeal = mode;
+            let mut game = Game::init(conf.clone(), Some(5)).unwrap();
+            let deck = game.first_pile().unwrap();
+            assert_eq!(game.piles[deck + 1].cards.len(), 3);
+            while !game.piles[deck].cards.is_empty() {
+                game.deal();
+            }
+            let waste = game.piles[deck + 1].cards.clone();
+            game.deal();
+            // the second pass deals by one card
+            assert_eq!(game.piles[deck + 1].cards.len(), 1);
+            assert_eq!(game.piles[deck + 1].cards[0].face, waste[first_out].face);
+            assert_eq!(game.piles[deck + 1].cards[0].suit, waste[first_out].suit);
+        }
+
+        let pconf = conf.pile.as_mut().unwrap();
+        (pconf.redeal, pconf.pile_to_cols) = (RedealMode::Shuffle, true);
+        conf.cols = cols(13, 13, 4);
+        let mut game = Game::init(conf, Some(5)).unwrap();
+        let first = game.first_col().unwrap();
+        game.piles[first].cards.truncate(5);
+        let before: Vec<String> = game.piles.iter().map(|p| cards_to_str(&p.cards)).collect();
+        assert!(game.can_deal());
+        game.apply(Move::Redeal).unwrap();
+        assert_eq!(game.redeal_left(), 0);
+        let lens: Vec<usize> = (first..first + 4).map(|col| game.piles[col].cards.len()).collect();
+        assert_eq!(lens, vec![13, 13, 13, 5]);
+        assert!(game.piles[first + 3].cards.iter().all(|c| c.is_up()));
+        assert!(!game.can_deal());
+        game.undo();
+        let after: Vec<String> = game.piles.iter().map(|p| cards_to_str(&p.cards)).collect();
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn deal_to_selected_cols() {
+        let mut conf = klondike_conf();
+        conf.name = "Spider".to_string();
+        let pconf = conf.pile.as_mut().unwrap();
+        (pconf.deal_by, pconf.redeals, pconf.pile_to_cols, pconf.deal_cols) = (2, 0, true, DealCols::Empty);
+        conf.cols = cols(5, 1, 4);
+        let lens = |game: &Game| -> Vec<usize> {
+            let first = game.first_col().unwrap();
+            (first..first + 4).map(|col| game.piles[col].cards.len()).collect()
+        };
+        let mut game = Game::init(conf.clone(), Some(5)).unwrap();
+        assert!(!game.can_deal());
+        assert_eq!(game.deal_block(), Some(DealBlock::NoTarget));
+        let first = game.first_col().unwrap();
+        game.piles[first + 1].cards.clear();
+        assert!(game.deal());
+        assert_eq!(lens(&game), vec![5, 1, 5, 5]);
+
+        for (deal_cols, expected) in
+            [(DealCols::Subset(0b1010), vec![5, 6, 5, 6]), (DealCols::Single(2), vec![5, 5, 7, 5])]
+        {
+            conf.pile.as_mut().unwrap().deal_cols = deal_cols;
+            let mut game = Game::init(conf.clone(), Some(5)).unwrap();
+            assert!(game.deal());
+            assert_eq!(lens(&game), expected);
+        }
+
+        conf.pile.as_mut().unwrap().deal_cols = DealCols::Single(4);
+        assert!(conf.validate().is_err());
+        let pconf = conf.pile.as_mut().unwrap();
+        (pconf.deal_cols, pconf.require_filled) = (DealCols::Empty, true);
+        assert!(conf.validate().is_err());
+    }
+
+    fn pairing_conf(collect: Collect, layout: Layout, cols: usize, pile_to_cols: bool) -> Conf {
+        use crate::gconf::FndSlot;
+        let mut conf = klondike_conf();
+        conf.name = "Pairs".to_string();
+        conf.playable = Playable::Top;
+        let pconf = conf.pile.as_mut().unwrap();
+        (pconf.redeals, pconf.pile_to_cols) = (0, pile_to_cols);
+        conf.fnd = vec![FndSlot {
+            first: Face::Any,
+            suit: Suit::Any,
+            forder: FaceOrder::Any,
+            sorder: SuitOrder::Any,
+            filler: None,
+        }];
+        (conf.collect, conf.goal, conf.layout) = (collect, Goal::Tableau, layout);
+        conf.cols = vec![ColConf { take_only: true, ..ColConf::new(1, 1) }; cols];
+        (conf.col_forder, conf.col_sorder, conf.col_refill) = (FaceOrder::Any, SuitOrder::Any, Face::Unavail);
+        conf
+    }
+
+    #[test]
+    fn pyramid_pairs() {
+        let layout = Layout::Free(crate::gconf::pyramid_places(3));
+        let mut game = Game::init(pairing_conf(Collect::Sum(13), layout, 6, false), Some(5)).unwrap();
+        let first = game.first_col().unwrap();
+        let waste = game.first_pile().unwrap() + 1;
+        let faces = [Face::K, Face::N5, Face::Q, Face::N8, Face::N9, Face::A];
+        for (idx, face) in faces.iter().enumerate() {
+            game.piles[first + idx].cards = vec![up_card(Suit::Spade, *face)];
+        }
+        game.piles[waste].cards = vec![up_card(Suit::Heart, Face::N4)];
+        assert!(game.is_covered(first) && game.is_covered(first + 1) && !game.is_covered(first + 3));
+        let moves = game.legal_moves();
+        assert!(moves.contains(&Move::Pair { a: first + 4, b: waste }));
+        // 8 and 5 sum to 13, but 5 is covered
+        assert!(!moves.iter().any(|m| matches!(m, Move::Pair { a, .. } if *a == first + 1)));
+
+        game.apply(Move::Pair { a: first + 4, b: waste }).unwrap();
+        assert!(game.piles[first + 4].cards.is_empty() && game.piles[waste].cards.is_empty());
+        assert_eq!(game.fnd(0).unwrap().len(), 2);
+        assert!(entry_to_str(&game.log()[0]).ends_with(&format!("pair {} {}", first + 4, waste)));
+        game.apply(Move::Pair { a: first + 3, b: first + 5 }).unwrap_err();
+        game.undo();
+        assert_eq!(game.piles[first + 4].cards.len(), 1);
+        assert!(game.fnd(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn covered_card_revealed() {
+        let layout = Layout::Free(crate::gconf::places_from_rows(&[vec![1], vec![0, 2]]));
+        let mut conf = pairing_conf(Collect::Sum(13), layout, 3, false);
+        conf.cols[0].up = 0;
+        let mut game = Game::init(conf, Some(5)).unwrap();
+        let first = game.first_col().unwrap();
+        assert!(!game.piles[first].cards[0].is_up() && game.piles[first + 1].cards[0].is_up());
+        game.piles[first + 1].cards = vec![up_card(Suit::Club, Face::N6)];
+        game.piles[first + 2].cards = vec![up_card(Suit::Club, Face::N7)];
+        game.apply(Move::Pair { a: first + 1, b: first + 2 }).unwrap();
+        assert!(game.piles[first].cards[0].is_up() && !game.is_covered(first));
+        game.undo();
+        assert!(!game.piles[first].cards[0].is_up() && game.is_covered(first));
+    }
+
+    #[test]
+    fn grid_neighbors_and_deal() {
+        let mut game = Game::init(pairing_conf(Collect::SameFace, Layout::Grid(3), 9, true), Some(5)).unwrap();
+        let first = game.first_col().unwrap();
+        let faces = [Face::N2, Face::N3, Face::N4, Face::N5, Face::N2, Face::N6, Face::N7, Face::N8, Face::N2];
+        for (idx, face) in faces.iter().enumerate() {
+            game.piles[first + idx].cards = vec![up_card(Suit::Spade, *face)];
+        }
+        assert!(!game.can_deal());
+        let moves = game.legal_moves();
+        // the corners are not neighbors
+        assert!(moves.contains(&Move::Pair { a: first, b: first + 4 }));
+        assert!(!moves.contains(&Move::Pair { a: first, b: first + 8 }));
+        game.apply(Move::Pair { a: first + 4, b: first + 8 }).unwrap();
+        assert!(game.can_deal());
+        game.apply(Move::Deal).unwrap();
+        let after: Vec<Face> = (0..7).map(|idx| game.piles[first + idx].cards[0].face).collect();
+        assert!(after
+            .iter()
+            .zip([Face::N2, Face::N3, Face::N4, Face::N5, Face::N6, Face::N7, Face::N8])
+            .all(|(a, b)| *a == b));
+        assert!(!game.piles[first + 8].cards.is_empty());
+    }
+
+    #[test]
+    fn ace_high_and_wrap() {
+        use crate::card::{Ace, RankOrder};
+        let fits = |wrap: bool, ace: Ace, card: Card, base: Card| {
+            let mut conf = klondike_conf();
+            conf.rank_order = RankOrder { wrap, ace };
+            conf.cols = cols(1, 1, 2);
+            let mut game = Game::init(conf, Some(5)).unwrap();
+            let col = game.first_col().unwrap();
+            (game.piles[col].cards, game.piles[col + 1].cards) = (vec![base], vec![card]);
+            game.can_move(Pos { col: col + 1, row: 0 }, col)
+        };
+        let (ace, two) = (up_card(Suit::Spade, Face::A), up_card(Suit::Spade, Face::N2));
+        let (red_ace, red_king) = (up_card(Suit::Heart, Face::A), up_card(Suit::Heart, Face::K));
+        assert!(fits(false, Ace::Low, red_ace, two));
+        assert!(!fits(false, Ace::Low, red_king, ace));
+        assert!(!fits(false, Ace::High, red_ace, two));
+        assert!(fits(false, Ace::High, red_king, ace));
+        // with wrapping, K and A are neighbors whatever the ace rank is
+        assert!(fits(true, Ace::Low, red_ace, two));
+        assert!(fits(true, Ace::Low, red_king, ace));
+        assert!(fits(true, Ace::High, red_ace, two));
+        assert!(fits(true, Ace::High, red_king, ace));
+    }
+
+    #[test]
+    fn hash_keeps_col_rules() {
+        let mut conf = klondike_conf();
+        conf.cols = cols(1, 1, 2);
+        let swapped_hashes = |conf: Conf| {
+            let mut game = Game::init(conf, Some(5)).unwrap();
+            let col = game.first_col().unwrap();
+            game.piles[col].cards = vec![up_card(Suit::Heart, Face::N5)];
+            game.piles[col + 1].cards.clear();
+            let before = game.state_hash();
+            game.piles[col + 1].cards = std::mem::take(&mut game.piles[col].cards);
+            (before, game.state_hash())
+        };
+        let (before, after) = swapped_hashes(conf.clone());
+        assert_eq!(before, after);
+        // an empty column that accepts any card differs from the one that accepts only kings
+        conf.cols[1].rules.refill = Some(Face::Any);
+        let (before, after) = swapped_hashes(conf);
+        assert_ne!(before, after);
+    }
 }
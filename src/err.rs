@@ -2,8 +2,18 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum SolError {
-    #[error("Invalid number of decks: {0}. Must be 1 or 2")]
-    InvalidDeckNumber(u8),
+    #[error("Invalid number of decks: {0}. Must be between 1 and {1}")]
+    InvalidDeckNumber(u8, u8),
+    #[error("Invalid number of jokers: {0}. Must be between 0 and 2")]
+    InvalidJokerNumber(u8),
+    #[error("Jokers can be used only if foundations are built card by card")]
+    JokersNotSupported,
+    #[error("Cards can be taken back only from foundations that are built card by card")]
+    InvalidConfFndPlayable,
+    #[error("Deck must contain at least one rank and one suit")]
+    EmptyDeck,
+    #[error("Card face {0} is not in the deck")]
+    FaceNotInDeck(String),
     #[error("Invalid number of temporary slots: {0}. Must be between 0 and 4")]
     InvalidTempNumber(u8),
     #[error("Invalid number of columns: {0}. Must be between 1 and 10")]
@@ -38,6 +48,8 @@ pub enum SolError {
     InvalidSuit(String),
     #[error("Invalid card face: {0}")]
     InvalidFace(String),
+    #[error("Invalid card: {0}")]
+    InvalidCard(String),
     #[error("Invalid card suit order: {0}")]
     InvalidSuitOrder(String),
     #[error("Invalid card face order: {0}")]
@@ -59,12 +71,30 @@ pub enum SolError {
     InvalidConfTempLimit,
     #[error("Invalid configuration: refillable temp slot must define sort order for card face and/or for card suit")]
     InvalidConfTempOrder,
+    #[error("Invalid configuration: refillable temp slot gets {0} cards at deal but holds at most {1}")]
+    InvalidConfTempDeal(u8, u8),
     #[error("Invalid temp configuration: only one slot can be refillable")]
     InvalidConfTempSingleRefillable,
+    #[error("Invalid pyramid: {0} columns do not make a pyramid of at most 7 rows")]
+    InvalidConfPyramid(usize),
+    #[error("Invalid layout: {0} places defined for {1} columns")]
+    InvalidConfPlaceCount(usize, usize),
+    #[error("Invalid layout: column {0} is out of play area or it is covered by a non-existing column")]
+    InvalidConfPlace(usize),
+    #[error("Invalid grid: {0} columns do not fit a grid of width {1}(width must be between 2 and 6, at most 5 rows)")]
+    InvalidConfGrid(usize, u8),
+    #[error("Invalid deck configuration: cards cannot be dealt to columns and foundation at the same time")]
+    InvalidConfDealTarget,
     #[error("File does not exist")]
     InvalidFileName,
     #[error("Reading rules from file failed")]
     FailedToOpenRules,
+    #[error("Saved game does not match the solitaire rules")]
+    InvalidSavedGame,
+    #[error("Invalid move record: {0}")]
+    InvalidMoveEntry(String),
+    #[error("Invalid replay: {0}")]
+    InvalidReplay(String),
 
     #[error("Invalid terminal size {0}x{1} (must be at least 60x25)")]
     InvalidTermSize(u16, u16),
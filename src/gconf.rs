@@ -1,7 +1,7 @@
-use crate::card::{Card, Face, Suit};
+use crate::card::{face_to_code, Card, DeckKind, Face, RankOrder, Suit};
 use crate::err::SolError;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Hash)]
 pub enum FaceOrder {
     Asc,
     Desc,
@@ -17,7 +17,7 @@ pub fn str_to_face_order(s: &str) -> Result<FaceOrder, SolError> {
     }
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Hash)]
 pub enum SuitOrder {
     SameSuit,
     SameColor,
@@ -39,12 +39,34 @@ pub fn str_to_suit_order(s: &str) -> Result<SuitOrder, SolError> {
     }
 }
 
+// how the empty deck is refilled when a redeal is left
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RedealMode {
+    Turn,    // the waste is turned over: cards come out in the same order as during the previous pass
+    Keep,    // the waste goes to the deck in reverse order: the last dealt card comes out first
+    Shuffle, // cards of columns and the waste are gathered, shuffled and dealt to columns again
+}
+
+// which columns get cards when the deck deals to columns
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DealCols {
+    All,         // one card to every column that is not take-only
+    Empty,       // one card to every empty column
+    Subset(u32), // one card to every column of the set: bit N stands for the column N
+    Single(u8),  // `deal_by` cards to the only column N
+}
+
 // deck pile configuration
 #[derive(Clone, Copy)]
 pub struct PileConf {
-    pub deal_by: u8,        // how many cards to move from deck to waste at a time
-    pub redeals: i8,        // redeals left
-    pub pile_to_cols: bool, // deal to columns instead of waste
+    pub deal_by: u8,          // how many cards to move from deck to waste at a time
+    pub redeal_by: u8,        // the same as deal_by but for the second and next passes(0 - equals deal_by)
+    pub redeals: i8,          // redeals left
+    pub redeal: RedealMode,   // how the deck is refilled
+    pub pile_to_cols: bool,   // deal to columns instead of waste
+    pub deal_cols: DealCols,  // what columns get cards if the deck deals to columns
+    pub pile_to_fnd: bool,    // deal to the first foundation instead of waste
+    pub require_filled: bool, // dealing to columns is forbidden while any column is empty
 }
 
 impl PileConf {
@@ -52,10 +74,106 @@ impl PileConf {
         if self.deal_by == 0 || self.deal_by > 16 {
             return Err(SolError::InvalidDealBy(self.deal_by));
         }
+        if self.redeal_by > 16 {
+            return Err(SolError::InvalidDealBy(self.redeal_by));
+        }
+        if self.pile_to_cols && self.pile_to_fnd {
+            return Err(SolError::InvalidConfDealTarget);
+        }
+        if self.require_filled && self.deal_cols == DealCols::Empty {
+            return Err(SolError::InvalidConfOptionValue("deal_cols".to_string(), "empty".to_string()));
+        }
         Ok(())
     }
 }
 
+// how cards get to foundations
+#[derive(Clone, Copy, PartialEq)]
+pub enum Collect {
+    Card,     // a player moves cards one by one
+    Sequence, // a full K-to-A run of the same suit is removed from a column automatically
+    Sum(u8),  // two cards which values sum to N are removed together, a card of value N is removed alone
+    SameFace, // two cards of the same face are removed together
+}
+
+// the largest position of a column in a free layout
+pub const MAX_PLACE_X: u8 = 20;
+pub const MAX_PLACE_Y: u8 = 6;
+
+// position of a column in a free layout and the columns that cover it
+#[derive(Clone, PartialEq, Debug)]
+pub struct ColPlace {
+    pub x: u8, // horizontal position in halves of a card width
+    pub y: u8, // vertical position in rows: a card of the next row covers the lower part of the card
+    // indices of the columns that lie on top of this one. Until all of them are empty, the column
+    // is unplayable, and its cards stay face-down if they were dealt so
+    pub covered_by: Vec<usize>,
+}
+
+// how columns are placed in play area
+#[derive(Clone, PartialEq, Debug)]
+pub enum Layout {
+    Columns, // side by side
+    // columns hold a single card each and make a grid with the given width. Cards can be paired
+    // only if they are neighbors, and dealing moves cards to fill gaps first
+    Grid(u8),
+    // every column has its own position and may be covered by other columns(e.g., Pyramid or TriPeaks)
+    Free(Vec<ColPlace>),
+}
+
+// build a free layout from rows of cards. Every row is a list of horizontal card positions.
+// A card is covered by the cards of the next row that overlap it: their positions differ by 1.
+// Columns are numbered row by row from the top one
+pub fn places_from_rows(rows: &[Vec<u8>]) -> Vec<ColPlace> {
+    let mut places = Vec::new();
+    let mut first_next = 0usize;
+    for (y, row) in rows.iter().enumerate() {
+        first_next += row.len();
+        for x in row.iter() {
+            let mut covered_by = Vec::new();
+            if let Some(next) = rows.get(y + 1) {
+                for (idx, nx) in next.iter().enumerate() {
+                    if nx.abs_diff(*x) == 1 {
+                        covered_by.push(first_next + idx);
+                    }
+                }
+            }
+            places.push(ColPlace { x: *x, y: y as u8, covered_by });
+        }
+    }
+    places
+}
+
+// a free layout of a pyramid made of `rows` rows: the first row has 1 card, the second one has 2
+// cards etc. Every card is covered by two cards of the next row
+pub fn pyramid_places(rows: usize) -> Vec<ColPlace> {
+    let rows: Vec<Vec<u8>> =
+        (0..rows).map(|r| (0..=r).map(|i| (rows - 1 - r + 2 * i) as u8).collect::<Vec<u8>>()).collect();
+    places_from_rows(&rows)
+}
+
+// returns the number of rows of a pyramid made of `count` cards: the first row has 1 card, the
+// second one has 2 cards etc. Returns None if the cards do not make a full pyramid
+pub fn pyramid_rows(count: usize) -> Option<usize> {
+    let (mut rows, mut total) = (0usize, 0usize);
+    while total < count {
+        rows += 1;
+        total += rows;
+    }
+    if total == count {
+        Some(rows)
+    } else {
+        None
+    }
+}
+
+// what cards must be moved to foundations to win
+#[derive(Clone, Copy, PartialEq)]
+pub enum Goal {
+    AllCards, // all cards of all piles
+    Tableau,  // only cards of columns, free cells and reserve: the deck and waste may be non-empty
+}
+
 // foundation pile configuration
 #[derive(Clone, Copy)]
 pub struct FndSlot {
@@ -66,19 +184,91 @@ pub struct FndSlot {
     pub filler: Option<Card>, // the foundation will have this card at start (i.e, the pile is never empty)
 }
 
+// which temp slots accept cards from other piles
+#[derive(Clone, Copy, PartialEq)]
+pub enum TempRefill {
+    All,      // every slot(classic free cells)
+    Nothing,  // slots are reserves: cards can be only taken from them
+    Slot(u8), // only one slot(zero-based index), others are reserves
+}
+
 // free-cell configuration
 #[derive(Clone, Copy)]
 pub struct TempConf {
-    pub count: u8, // the number of free cells
+    pub count: u8,                             // the number of free cells
+    pub limit: u8,                             // maximum number of cards in a slot (0 - unlimited)
+    pub order: Option<(FaceOrder, SuitOrder)>, // how cards are stacked in a slot holding more than one card
+    pub deal: u8,                              // the number of face-up cards dealt to every slot at start
+    pub refill: TempRefill,                    // which slots accept cards
 }
 
 impl TempConf {
+    // classic free cells: every slot holds a single card
+    pub fn new(count: u8) -> Self {
+        TempConf { count, limit: 1, order: None, deal: 0, refill: TempRefill::All }
+    }
+
     pub fn validate(&self) -> Result<(), SolError> {
         if self.count > 4 {
             return Err(SolError::InvalidTempNumber(self.count));
         }
+        match self.refill {
+            TempRefill::Nothing => return Ok(()),
+            TempRefill::Slot(n) if n >= self.count => {
+                return Err(SolError::InvalidConfOptionValue("refillable".to_string(), (n + 1).to_string()))
+            }
+            _ => {}
+        }
+        // a slot without a limit is just another column
+        if self.limit == 0 {
+            return Err(SolError::InvalidConfTempLimit);
+        }
+        if self.deal > self.limit {
+            return Err(SolError::InvalidConfTempDeal(self.deal, self.limit));
+        }
+        if self.limit > 1 && self.order.is_none() {
+            return Err(SolError::InvalidConfTempOrder);
+        }
         Ok(())
     }
+
+    // returns true if a player can put cards to the slot
+    pub fn is_refillable(&self, slot: usize) -> bool {
+        match self.refill {
+            TempRefill::All => true,
+            TempRefill::Nothing => false,
+            TempRefill::Slot(n) => usize::from(n) == slot,
+        }
+    }
+}
+
+// reserve configuration: a stack of cards where only the top card is face-up and playable
+#[derive(Clone, Copy)]
+pub struct ReserveConf {
+    pub count: u8,         // initial number of cards
+    pub up: u8,            // initial number of face-up cards
+    pub refill_cols: bool, // the top card is moved to a column automatically when the column gets empty
+}
+
+impl ReserveConf {
+    pub fn validate(&self) -> Result<(), SolError> {
+        if self.count == 0 {
+            return Err(SolError::InvalidConfOptionValue("cards".to_string(), self.count.to_string()));
+        }
+        if self.up == 0 || self.up > self.count {
+            return Err(SolError::InvalidConfOptionValue("up".to_string(), self.up.to_string()));
+        }
+        Ok(())
+    }
+}
+
+// column rules that override the common ones from `Conf`. None means the common rule is used
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct ColRules {
+    pub forder: Option<FaceOrder>,
+    pub sorder: Option<SuitOrder>,
+    pub refill: Option<Face>,
+    pub playable: Option<Playable>,
 }
 
 // column configuration
@@ -87,14 +277,26 @@ pub struct ColConf {
     pub count: u8,       // initial number of cards
     pub up: u8,          // initial number of face-up cards
     pub take_only: bool, // a user cannot put cards to the pile, only take from it
+    pub rules: ColRules, // rules that differ from the common column ones
+}
+
+impl ColConf {
+    // a column that accepts cards and follows the common column rules
+    pub fn new(count: u8, up: u8) -> Self {
+        ColConf { count, up, take_only: false, rules: ColRules::default() }
+    }
 }
 
 // which cards can be move from a column to another pile
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Hash)]
 pub enum Playable {
-    Top,     // only the top one
-    Any,     // any number of face-up cards
-    Ordered, // any number of face-up cards from the top of a pile if the cards are in order
+    Top,      // only the top one
+    Any,      // any number of face-up cards
+    Ordered,  // any number of face-up cards from the top of a pile if the cards are in order
+    SameSuit, // the same as Ordered, but all cards must be of the same suit as well
+    // the same as Ordered, but the number of cards is limited by free cells and empty columns:
+    // (free cells + 1) * 2^(empty columns)
+    Supermove,
 }
 
 #[derive(Clone)]
@@ -102,15 +304,23 @@ pub struct Conf {
     pub chance: Option<u16>, // chance of winning 1 of N (if known)
     pub name: String,        // solitaire unique name
 
-    pub deck_count: u8,     // number of decks (1 or 2)
-    pub playable: Playable, // what cards in a column are playable
+    pub deck_count: u8,        // number of decks
+    pub deck: DeckKind,        // ranks and suits of a single deck
+    pub rank_order: RankOrder, // whether K and A are neighbors and whether A is high or low
+    pub playable: Playable,    // what cards in a column are playable
 
-    pub pile: Option<PileConf>, // deck and waste configuration
-    pub fnd: Vec<FndSlot>,      // foundation configuration
-    pub temp: Option<TempConf>, // number of temp slots
-    pub cols: Vec<ColConf>,     // column configuration
-    pub col_forder: FaceOrder,  // face order of cards in columns
-    pub col_sorder: SuitOrder,  // suit order of cards in columns
+    pub pile: Option<PileConf>,       // deck and waste configuration
+    pub fnd: Vec<FndSlot>,            // foundation configuration
+    pub collect: Collect,             // how cards are moved to foundations
+    pub fnd_playable: bool,           // the top card of a foundation can be moved back to the tableau
+    pub fnd_penalty: u16,             // points lost for every card taken back from a foundation
+    pub goal: Goal,                   // what cards must be moved to foundations to win
+    pub layout: Layout,               // how columns are placed
+    pub temp: Option<TempConf>,       // number of temp slots
+    pub reserve: Option<ReserveConf>, // reserve pile
+    pub cols: Vec<ColConf>,           // column configuration
+    pub col_forder: FaceOrder,        // face order of cards in columns
+    pub col_sorder: SuitOrder,        // suit order of cards in columns
     // Face of card that must start a pile when it gets empty.
     // Unavail: empty col cannot be filled
     pub col_refill: Face,
@@ -122,10 +332,18 @@ impl Default for Conf {
             name: String::new(),
             chance: None,
             deck_count: 1,
+            deck: DeckKind::full(),
+            rank_order: RankOrder::default(),
             playable: Playable::Top,
             pile: None,
             fnd: Vec::new(),
+            collect: Collect::Card,
+            fnd_playable: false,
+            fnd_penalty: 0,
+            goal: Goal::AllCards,
+            layout: Layout::Columns,
             temp: None,
+            reserve: None,
             cols: Vec::new(),
             col_forder: FaceOrder::Desc,
             col_sorder: SuitOrder::SameSuit,
@@ -138,9 +356,22 @@ impl Conf {
     pub fn new() -> Self {
         Default::default()
     }
+    // a regular face that a stripped deck does not have makes the game unwinnable
+    fn check_in_deck(&self, face: Face) -> Result<(), SolError> {
+        match face {
+            Face::Empty | Face::Any | Face::Unavail | Face::Column => Ok(()),
+            f if self.deck.has_face(f) => Ok(()),
+            f => Err(SolError::FaceNotInDeck(face_to_code(f).to_string())),
+        }
+    }
     pub fn validate(&self) -> Result<(), SolError> {
-        if self.deck_count == 0 || self.deck_count > 2 {
-            return Err(SolError::InvalidDeckNumber(self.deck_count));
+        self.deck.validate(self.deck_count)?;
+        if self.deck.jokers != 0 && self.collect != Collect::Card {
+            return Err(SolError::JokersNotSupported);
+        }
+        let deals_to_fnd = self.pile.is_some_and(|p| p.pile_to_fnd);
+        if self.fnd_playable && (self.collect != Collect::Card || deals_to_fnd) {
+            return Err(SolError::InvalidConfFndPlayable);
         }
         if let Some(ref cfg) = self.temp {
             cfg.validate()?;
@@ -155,12 +386,56 @@ impl Conf {
             if w.first == Face::Empty {
                 return Err(SolError::NoFoundationStart);
             }
+            self.check_in_deck(w.first)?;
+            if let Some(c) = w.filler {
+                self.check_in_deck(c.face)?;
+                if !self.deck.suits.contains(&c.suit) {
+                    return Err(SolError::InvalidSuit(format!("{:?}", c.suit)));
+                }
+            }
+        }
+        self.check_in_deck(self.col_refill)?;
+        for col in &self.cols {
+            if let Some(face) = col.rules.refill {
+                self.check_in_deck(face)?;
+            }
         }
         if self.cols.is_empty() {
             return Err(SolError::NoCols);
         }
-        if self.cols.len() > 10 {
-            return Err(SolError::InvalidColNumber(self.cols.len() as u8));
+        let cnt = self.cols.len();
+        match self.layout {
+            Layout::Columns => {
+                if cnt > 10 {
+                    return Err(SolError::InvalidColNumber(cnt as u8));
+                }
+            }
+            Layout::Free(ref places) => {
+                if places.len() != cnt {
+                    return Err(SolError::InvalidConfPlaceCount(places.len(), cnt));
+                }
+                for (idx, place) in places.iter().enumerate() {
+                    let out = place.x > MAX_PLACE_X || place.y > MAX_PLACE_Y;
+                    if out || place.covered_by.iter().any(|&c| c >= cnt || c == idx) {
+                        return Err(SolError::InvalidConfPlace(idx + 1));
+                    }
+                }
+            }
+            Layout::Grid(w) => {
+                if !(2..=6).contains(&w) || cnt > usize::from(w) * 5 {
+                    return Err(SolError::InvalidConfGrid(cnt, w));
+                }
+            }
+        }
+        if let Some(pconf) = self.pile {
+            let bad = match pconf.deal_cols {
+                DealCols::Subset(mask) if mask == 0 || (cnt < 32 && mask >> cnt != 0) => Some("list".to_string()),
+                DealCols::Single(n) if usize::from(n) >= cnt => Some(format!("single {}", n + 1)),
+                _ => None,
+            };
+            if let Some(val) = bad {
+                return Err(SolError::InvalidConfOptionValue("deal_cols".to_string(), val));
+            }
         }
         Ok(())
     }
@@ -181,3 +456,36 @@ impl Conf {
         }
     }
 }
+
+// Klondike rules shared by tests of the library modules. A test changes only the options it checks
+#[cfg(test)]
+pub(crate) fn klondike_conf() -> Conf {
+    Conf {
+        name: "Klondike".to_string(),
+        playable: Playable::Any,
+        pile: Some(PileConf {
+            deal_by: 1,
+            redeal_by: 0,
+            redeals: -1,
+            redeal: RedealMode::Turn,
+            pile_to_cols: false,
+            deal_cols: DealCols::All,
+            pile_to_fnd: false,
+            require_filled: false,
+        }),
+        fnd: vec![
+            FndSlot {
+                first: Face::A,
+                suit: Suit::Any,
+                forder: FaceOrder::Asc,
+                sorder: SuitOrder::SameSuit,
+                filler: None
+            };
+            4
+        ],
+        cols: (1..=7).map(|count| ColConf::new(count, 1)).collect(),
+        col_sorder: SuitOrder::AlternateColor,
+        col_refill: Face::K,
+        ..Conf::default()
+    }
+}
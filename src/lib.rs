@@ -2,3 +2,5 @@ pub mod card;
 pub mod engine;
 pub mod err;
 pub mod gconf;
+pub mod replay;
+pub mod solver;
@@ -0,0 +1,134 @@
+use crate::engine::{entry_to_str, str_to_entry, Game, LogEntry};
+use crate::err::SolError;
+use crate::gconf::Conf;
+
+const MOVES_SECTION: &str = "[moves]";
+
+// a recorded game that can be played back move by move.
+// Text format:
+//   name = <solitaire name>
+//   deal = <deal number>
+//   [moves]
+//   <one move per line, see `entry_to_str`>
+// Empty lines and lines starting with '#' are skipped
+pub struct Replay {
+    pub name: String, // solitaire name
+    pub seed: u64,    // deal number
+    pub moves: Vec<LogEntry>,
+}
+
+impl Replay {
+    // record all moves made in a game so far
+    pub fn from_game(game: &Game) -> Replay {
+        Replay { name: game.rules_name().to_string(), seed: game.deal_number(), moves: game.log().to_vec() }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("# solkit replay\nname = {}\ndeal = {}\n\n{}\n", self.name, self.seed, MOVES_SECTION);
+        for entry in self.moves.iter() {
+            text.push_str(&entry_to_str(entry));
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn parse(text: &str) -> Result<Replay, SolError> {
+        let mut name: Option<String> = None;
+        let mut seed: Option<u64> = None;
+        let mut moves = Vec::new();
+        let mut in_moves = false;
+        for line in text.lines() {
+            let line = line.trim_start_matches('\u{feff}').trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if in_moves {
+                moves.push(str_to_entry(line)?);
+                continue;
+            }
+            if line.to_lowercase() == MOVES_SECTION {
+                in_moves = true;
+                continue;
+            }
+            let pos = match line.find('=') {
+                Some(n) => n,
+                None => return Err(SolError::InvalidReplay(line.to_string())),
+            };
+            let opt_val = line[pos + 1..].trim();
+            match line[..pos].trim().to_lowercase().as_str() {
+                "name" => name = Some(opt_val.to_string()),
+                "deal" => match opt_val.parse::<u64>() {
+                    Ok(n) => seed = Some(n),
+                    Err(_) => return Err(SolError::InvalidReplay(line.to_string())),
+                },
+                _ => return Err(SolError::InvalidReplay(line.to_string())),
+            }
+        }
+        match (name, seed) {
+            (Some(name), Some(seed)) => Ok(Replay { name, seed, moves }),
+            (None, _) => Err(SolError::InvalidReplay("solitaire name is missing".to_string())),
+            (_, None) => Err(SolError::InvalidReplay("deal number is missing".to_string())),
+        }
+    }
+
+    // check that all moves can be made in the right order
+    pub fn validate(&self, conf: &Conf) -> Result<(), SolError> {
+        let mut game = Game::init(conf.clone(), Some(self.seed))?;
+        for (idx, entry) in self.moves.iter().enumerate() {
+            if game.exec(entry.mv).is_err() {
+                return Err(SolError::InvalidReplay(format!("move {} cannot be made", idx + 1)));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod replay_test {
+    use super::*;
+    use crate::gconf::klondike_conf;
+    use std::time::Duration;
+
+    #[test]
+    fn text_roundtrip() {
+        let conf = klondike_conf();
+        let mut game = Game::init(conf.clone(), Some(77)).unwrap();
+        for step in 0..40u64 {
+            let moves = game.legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+            game.set_clock(Duration::from_millis(step * 1250));
+            game.apply(moves[0]).unwrap();
+        }
+        game.undo();
+        let replay = Replay::from_game(&game);
+        assert_eq!(replay.moves.len(), game.log().len());
+        let text = replay.to_text();
+        let back = Replay::parse(&text).unwrap();
+        assert_eq!(back.name, "Klondike");
+        assert_eq!(back.seed, 77);
+        assert_eq!(back.moves, replay.moves);
+        assert!(back.validate(&conf).is_ok());
+
+        assert!(Replay::parse("name = Klondike\n[moves]\n0.5 deal\n").is_err());
+        assert!(Replay::parse("name = Klondike\ndeal = 1\n[moves]\n0.5 jump\n").is_err());
+    }
+
+    #[test]
+    fn illegal_move_rejected() {
+        use crate::card::Face;
+        use crate::gconf::{ColConf, Playable};
+        let mut conf = klondike_conf();
+        (conf.playable, conf.pile, conf.col_refill) = (Playable::Ordered, None, Face::Any);
+        conf.cols = vec![ColConf::new(13, 13); 4];
+        conf.cols.push(ColConf::new(0, 0));
+        let first = Game::init(conf.clone(), Some(3)).unwrap().first_col().unwrap();
+        // the whole column goes to the empty one, but its cards are not in order
+        let text = format!("name = Klondike\ndeal = 3\n[moves]\n0.5 move {} {} 13\n", first, first + 4);
+        let replay = Replay::parse(&text).unwrap();
+        assert!(replay.validate(&conf).is_err());
+        let text = format!("name = Klondike\ndeal = 3\n[moves]\n0.5 move {} {} 1\n", first, first + 4);
+        assert!(Replay::parse(&text).unwrap().validate(&conf).is_ok());
+    }
+}
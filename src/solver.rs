@@ -0,0 +1,298 @@
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub use crate::engine::Move;
+use crate::engine::{Game, Pos, SlotConf};
+use crate::err::SolError;
+use crate::gconf::Conf;
+
+// how often(in checked positions) the solver looks at the clock
+const TIME_CHECK_EVERY: usize = 1024;
+
+// search limits. The solver gives up when any of them is exceeded
+#[derive(Clone, Copy)]
+pub struct Limits {
+    pub nodes: usize,           // maximum number of positions to check (0 - unlimited)
+    pub time: Option<Duration>, // maximum search time
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits { nodes: 1_000_000, time: Some(Duration::from_secs(60)) }
+    }
+}
+
+pub enum Outcome {
+    Solved(Vec<Move>), // the list of moves to win the game
+    Unsolvable,        // all positions checked, the game cannot be won
+    GaveUp,            // search limits exceeded
+}
+
+pub struct Solution {
+    pub outcome: Outcome,
+    pub nodes: usize,      // the number of checked positions
+    pub elapsed: Duration, // search time
+}
+
+impl Solution {
+    // return the list of moves to win the game if a solution is found
+    pub fn moves(&self) -> Option<&[Move]> {
+        match self.outcome {
+            Outcome::Solved(ref moves) => Some(moves),
+            _ => None,
+        }
+    }
+}
+
+// results of solving many deals of the same solitaire
+#[derive(Default)]
+pub struct Estimate {
+    pub deals: usize,      // total number of solved deals
+    pub solved: usize,     // deals that can be won
+    pub unsolvable: usize, // deals that cannot be won
+    pub gave_up: usize,    // deals the solver failed to solve within limits
+}
+
+impl Estimate {
+    pub fn add(&mut self, solution: &Solution) {
+        self.deals += 1;
+        match solution.outcome {
+            Outcome::Solved(_) => self.solved += 1,
+            Outcome::Unsolvable => self.unsolvable += 1,
+            Outcome::GaveUp => self.gave_up += 1,
+        }
+    }
+
+    // fraction of won deals. Deals the solver gave up on are counted as lost ones
+    pub fn rate(&self) -> f64 {
+        if self.deals == 0 {
+            return 0.0;
+        }
+        self.solved as f64 / self.deals as f64
+    }
+
+    // 95% confidence interval of the fraction of won deals(Wilson score interval)
+    pub fn interval(&self) -> (f64, f64) {
+        if self.deals == 0 {
+            return (0.0, 1.0);
+        }
+        const Z: f64 = 1.96;
+        let n = self.deals as f64;
+        let p = self.rate();
+        let denom = 1.0 + Z * Z / n;
+        let center = (p + Z * Z / (2.0 * n)) / denom;
+        let margin = Z * (p * (1.0 - p) / n + Z * Z / (4.0 * n * n)).sqrt() / denom;
+        ((center - margin).max(0.0), (center + margin).min(1.0))
+    }
+
+    // chance of winning in the format of Conf::chance: 1 of N.
+    // Returns None if no deal has been won
+    pub fn chance(&self) -> Option<u16> {
+        if self.solved == 0 {
+            return None;
+        }
+        let n = (self.deals as f64 / self.solved as f64).round();
+        Some(n.min(f64::from(u16::MAX)) as u16)
+    }
+}
+
+// position in the search tree
+struct Frame {
+    game: Game,
+    moves: Vec<Move>,
+    next: usize,
+}
+
+// the order in which the solver tries moves: lower value goes first
+fn move_priority(game: &Game, mv: Move) -> u8 {
+    let (from, to, count) = match mv {
+        Move::Card { from, to, count } => (from, to, count),
+        Move::Pair { .. } => return 1,
+        _ => return 4,
+    };
+    if game.is_fnd(to) {
+        return 0;
+    }
+    if game.is_fnd(from) {
+        // taking a card back from a foundation helps only in rare cases
+        return 5;
+    }
+    let below = game.card_at(Pos { col: from, row: count });
+    if !below.is_empty() && !below.is_up() {
+        // the move opens a face-down card
+        return 1;
+    }
+    let emptied = below.is_empty();
+    let to_empty = game.slot_cards_left(to).unwrap_or(0) == 0;
+    match (emptied, to_empty) {
+        (true, false) => 2,
+        (true, true) => 5, // just moves the whole pile to another place
+        _ => 3,
+    }
+}
+
+// all moves that can be done in the current game position in order of priority
+fn gen_moves(game: &Game) -> Vec<Move> {
+    if let Some((from, to)) = game.safe_move() {
+        // moving safe cards to foundations never makes the position worse
+        return vec![Move::Card { from: from.col, to: to.col, count: 1 }];
+    }
+    let mut moves: Vec<(u8, Move)> = Vec::new();
+    // empty free cells are the same, as well as empty columns, if their rules are the same: a
+    // column with its own order or refill rule, or a free cell with its own limit, leads elsewhere
+    let mut seen: Vec<(usize, usize, SlotConf)> = Vec::new();
+    for mv in game.legal_moves() {
+        if let Move::Card { from, to, count } = mv {
+            if game.slot_cards_left(to).unwrap_or(0) == 0 && (game.is_temp(to) || game.is_col(to)) {
+                let key = (from, count, *game.slot_conf(to).expect("pile must have configuration"));
+                if seen.contains(&key) {
+                    continue;
+                }
+                seen.push(key);
+            }
+        }
+        moves.push((move_priority(game, mv), mv));
+    }
+    moves.sort_by_key(|m| m.0);
+    moves.into_iter().map(|m| m.1).collect()
+}
+
+// look for a solution of a game from its current position
+pub fn solve_game(game: &Game, limits: &Limits) -> Solution {
+    let start = Instant::now();
+    let mut nodes = 0usize;
+    let finish = |outcome: Outcome, nodes: usize| Solution { outcome, nodes, elapsed: start.elapsed() };
+    if game.is_completed() {
+        return finish(Outcome::Solved(Vec::new()), nodes);
+    }
+
+    let mut visited: HashSet<u64> = HashSet::new();
+    visited.insert(game.state_hash());
+    let mut path: Vec<Move> = Vec::new();
+    let mut stack: Vec<Frame> = vec![Frame { game: game.fork(), moves: gen_moves(game), next: 0 }];
+    while let Some(top) = stack.last_mut() {
+        if top.next >= top.moves.len() {
+            stack.pop();
+            path.pop();
+            continue;
+        }
+        if limits.nodes != 0 && nodes >= limits.nodes {
+            return finish(Outcome::GaveUp, nodes);
+        }
+        if nodes.is_multiple_of(TIME_CHECK_EVERY) {
+            if let Some(tm) = limits.time {
+                if start.elapsed() >= tm {
+                    return finish(Outcome::GaveUp, nodes);
+                }
+            }
+        }
+
+        let mv = top.moves[top.next];
+        top.next += 1;
+        let mut g = top.game.fork();
+        if g.exec(mv).is_err() {
+            continue;
+        }
+        nodes += 1;
+        if g.is_completed() {
+            path.push(mv);
+            return finish(Outcome::Solved(path), nodes);
+        }
+        if !visited.insert(g.state_hash()) {
+            continue;
+        }
+        let moves = gen_moves(&g);
+        path.push(mv);
+        stack.push(Frame { game: g, moves, next: 0 });
+    }
+    finish(Outcome::Unsolvable, nodes)
+}
+
+// look for a solution of a deal with a given number
+pub fn solve(conf: &Conf, seed: u64, limits: &Limits) -> Result<Solution, SolError> {
+    let game = Game::init(conf.clone(), Some(seed))?;
+    Ok(solve_game(&game, limits))
+}
+
+// solve deals with numbers from a given range. The callback is called after every deal
+pub fn estimate<F>(conf: &Conf, seeds: Range<u64>, limits: &Limits, mut progress: F) -> Result<Estimate, SolError>
+where
+    F: FnMut(u64, &Solution),
+{
+    let mut est = Estimate::default();
+    let conf = Arc::new(conf.clone());
+    for seed in seeds {
+        let game = Game::init(Arc::clone(&conf), Some(seed))?;
+        let solution = solve_game(&game, limits);
+        est.add(&solution);
+        progress(seed, &solution);
+    }
+    Ok(est)
+}
+
+#[cfg(test)]
+mod solver_test {
+    use super::*;
+    use crate::gconf::klondike_conf;
+
+    #[test]
+    fn solution_wins() {
+        let conf = klondike_conf();
+        let limits = Limits { nodes: 100_000, time: None };
+        let mut solved = 0;
+        for seed in 1..=3 {
+            let solution = solve(&conf, seed, &limits).unwrap();
+            let moves = match solution.moves() {
+                None => continue,
+                Some(m) => m,
+            };
+            solved += 1;
+            let mut game = Game::init(conf.clone(), Some(seed)).unwrap();
+            for mv in moves.iter() {
+                assert!(game.apply(*mv).is_ok());
+            }
+            assert!(game.is_completed());
+        }
+        assert!(solved != 0);
+    }
+
+    #[test]
+    fn empty_cols_with_own_rules() {
+        use crate::gconf::{ColConf, FaceOrder};
+        let mut conf = klondike_conf();
+        conf.col_refill = crate::card::Face::Any;
+        conf.cols.extend(vec![ColConf::new(0, 0); 2]);
+        let to_empty = |conf: &Conf| -> usize {
+            let game = (0..100)
+                .map(|seed| Game::init(conf.clone(), Some(seed)).unwrap())
+                .find(|g| g.safe_move().is_none())
+                .unwrap();
+            // the empty columns are the last two ones
+            let first = game.first_col().unwrap() + 7;
+            gen_moves(&game)
+                .iter()
+                .filter(|m| matches!(m, Move::Card { to, .. } if *to >= first && *to <= first + 1))
+                .count()
+        };
+        let same = to_empty(&conf);
+        assert!(same > 0);
+        // moves to the columns with different rules are kept apart
+        conf.cols[8].rules.forder = Some(FaceOrder::Asc);
+        assert_eq!(to_empty(&conf), same * 2);
+    }
+
+    #[test]
+    fn estimate_stats() {
+        let mut est = Estimate::default();
+        assert_eq!(est.chance(), None);
+        est.deals = 100;
+        est.solved = 25;
+        est.unsolvable = 75;
+        assert_eq!(est.chance(), Some(4));
+        let (low, high) = est.interval();
+        assert!(low < 0.25 && low > 0.15);
+        assert!(high > 0.25 && high < 0.35);
+    }
+}